@@ -14,16 +14,41 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    fs, io,
+    path::Path,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 use iced::{
-    event::Event, executor, subscription, widget::column, window, Application, Command, Element,
-    Renderer, Subscription, Theme,
+    event::Event,
+    executor, subscription,
+    widget::{button, checkbox, column, pick_list, row, scrollable, text, text_input, Column},
+    window, Application, Color, Command, Element, Length, Renderer, Subscription, Theme,
+};
+
+use checkers_lib::{
+    enums::{
+        CustomVariantError, EvaluationWeightsError, GameResult, NetworkError, OpeningBookError,
+        Piece, Side, TablebaseError, Variant,
+    },
+    structs::{
+        evaluators::{EvaluationWeights, HandcraftedEvaluator, Network, NetworkEvaluator},
+        Ballot, BallotDeck, Engine, GameData, GameRecord, Handicap, MoveAnalysis, OpeningBook,
+        Position, Tablebase,
+    },
+    traits::Evaluator,
 };
+use rand::seq::SliceRandom;
 
 use crate::application::{
     enums::Message,
-    structs::{Board, BoardMessage, GameData},
+    structs::{Board, BoardMessage, Evaluation},
 };
 
 pub struct Checkers {
@@ -31,15 +56,427 @@ pub struct Checkers {
     board: Board,
     /// Данные о состоянии игры
     game_data: Rc<RefCell<GameData>>,
+    /// Запись текущей партии
+    record: GameRecord,
+    /// Анализ ходов текущей партии. Пустой, если партия не анализировалась
+    analysis: Vec<MoveAnalysis>,
+    /// Идёт ли анализ партии
+    analysing: bool,
+    /// Сообщение о результате сохранения партии
+    export_message: Option<String>,
+    /// Разновидности игры, доступные для новой партии: встроенные и пользовательские
+    variants: Vec<Variant>,
+    /// Разновидность игры, выбранная для новой партии
+    selected_variant: Variant,
+    /// Описание ошибки загрузки пользовательских разновидностей игры
+    custom_variants_error: Option<String>,
+    /// Движок, который выбирает ходы за компьютер
+    engine: Engine,
+    /// Разновидность игры, для которой к движку подключены таблицы эндшпиля и дебютная книга
+    engine_variant: Option<Variant>,
+    /// Описания ошибок загрузки таблиц эндшпиля и дебютной книги
+    engine_data_errors: Vec<String>,
+    /// Оценка позиции по весам: встроенным или настроенным для текущей разновидности игры
+    handcrafted_evaluator: Arc<dyn Evaluator>,
+    /// Нейросетевая оценка позиции, если для текущей разновидности игры есть обученная сеть
+    network_evaluator: Option<Arc<dyn Evaluator>>,
+    /// Оценивать ли позицию нейросетью, если она есть
+    use_network: bool,
+    /// Сторона, за которую играет компьютер. None - если играют два человека
+    computer_side: Option<Side>,
+    /// Начинать ли партии с дебюта, выбранного жребием
+    start_from_ballot: bool,
+    /// Дебют, с которого началась текущая партия
+    ballot: Option<Ballot>,
+    /// Фора для новой партии
+    handicap: Handicap,
+    /// Начальная позиция новой партии в формате FEN. Пустая строка - обычная начальная позиция
+    fen: String,
+    /// Описание ошибки, из-за которой не удалось начать новую партию
+    new_game_error: Option<String>,
+    /// Оценивает ли движок текущую позицию в фоне
+    live_analysis: bool,
+    /// Флаг, по которому прерывается фоновый перебор устаревшей позиции
+    live_analysis_stop: Arc<AtomicBool>,
+    /// Включён ли редактор позиции
+    editing: bool,
+    /// Результат проверки позиции в редакторе
+    editor_message: Option<String>,
 }
 
 impl Default for Checkers {
     fn default() -> Self {
         let game_data = Rc::new(RefCell::new(GameData::default()));
+        let record = GameRecord::new(game_data.borrow().clone());
         Self {
             board: Board::new(game_data.clone()),
+            record,
             game_data,
+            analysis: Vec::new(),
+            analysing: false,
+            export_message: None,
+            variants: Variant::ALL.to_vec(),
+            selected_variant: Variant::default(),
+            custom_variants_error: None,
+            engine: Engine::default(),
+            engine_variant: None,
+            engine_data_errors: Vec::new(),
+            handcrafted_evaluator: Arc::new(HandcraftedEvaluator::default()),
+            network_evaluator: None,
+            use_network: true,
+            computer_side: None,
+            start_from_ballot: false,
+            ballot: None,
+            handicap: Handicap::default(),
+            fen: String::new(),
+            new_game_error: None,
+            live_analysis: false,
+            live_analysis_stop: Arc::new(AtomicBool::new(false)),
+            editing: false,
+            editor_message: None,
+        }
+    }
+}
+
+impl Checkers {
+    /// Ширина панели со списком ходов
+    const MOVE_LIST_WIDTH: f32 = 320.0;
+    /// Цвет комментариев к ходам, отмеченным при анализе партии
+    const JUDGEMENT_COLOR: Color = Color::from_rgb(0.8, 0.0, 0.0);
+    /// Файл, в который сохраняется партия в формате PDN, в текущей директории
+    const PDN_FILE: &'static str = "game.pdn";
+    /// Стороны, которые могут давать фору
+    const SIDES: [Side; 2] = [Side::White, Side::Black];
+    /// Наибольшее количество шашек, которые можно убрать при форе
+    const MAX_HANDICAP_MEN: u8 = 4;
+    /// Наибольшее количество лишних ходов при форе
+    const MAX_HANDICAP_MOVES: u8 = 3;
+    /// Файл с пользовательскими разновидностями игры, ищется в текущей директории
+    const CUSTOM_VARIANTS_FILE: &'static str = "variants.toml";
+    /// Директория с таблицами эндшпиля, ищется в текущей директории
+    const TABLEBASE_DIR: &'static str = "tablebases";
+    /// Директория с дебютными книгами, ищется в текущей директории
+    const BOOK_DIR: &'static str = "books";
+    /// Директория с настроенными весами оценки позиции, ищется в текущей директории
+    const WEIGHTS_DIR: &'static str = "weights";
+    /// Директория с обученными нейросетями оценки позиции, ищется в текущей директории
+    const NETWORK_DIR: &'static str = "networks";
+    /// Глубина, на которой останавливается фоновая оценка позиции
+    const LIVE_ANALYSIS_MAX_DEPTH: u32 = 16;
+
+    /// Загружает пользовательские разновидности игры и обновляет список доступных правил
+    fn load_variants(&mut self) {
+        self.variants = Variant::ALL.to_vec();
+        self.custom_variants_error = None;
+        match Variant::load_custom(Self::CUSTOM_VARIANTS_FILE) {
+            Ok(custom_variants) => self.variants.extend(custom_variants),
+            // Файл с пользовательскими правилами необязателен
+            Err(CustomVariantError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => self.custom_variants_error = Some(error.to_string()),
+        }
+        if !self.variants.contains(&self.selected_variant) {
+            self.selected_variant = Variant::default();
+        }
+    }
+
+    /// Подключает к движку таблицы эндшпиля, дебютную книгу, настроенные веса оценки
+    /// и нейросеть для указанной разновидности игры, если они есть
+    fn load_engine_data(&mut self, variant: &Variant) {
+        if self.engine_variant.as_ref() == Some(variant) {
+            return;
+        }
+        self.engine_variant = Some(variant.clone());
+        self.engine_data_errors.clear();
+        let mut engine = Engine::default();
+        self.handcrafted_evaluator = Arc::new(HandcraftedEvaluator::default());
+        let weights_path =
+            Path::new(Self::WEIGHTS_DIR).join(EvaluationWeights::default_file_name(variant));
+        match EvaluationWeights::load(weights_path) {
+            Ok(weights) => {
+                self.handcrafted_evaluator = Arc::new(HandcraftedEvaluator::new(weights))
+            }
+            // Без настроенных весов используются встроенные
+            Err(EvaluationWeightsError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => self.engine_data_errors.push(error.to_string()),
+        }
+        self.network_evaluator = None;
+        let network_path = Path::new(Self::NETWORK_DIR).join(Network::default_file_name(variant));
+        match Network::load(network_path, variant.clone()) {
+            Ok(network) => {
+                self.network_evaluator = Some(Arc::new(NetworkEvaluator::new(Arc::new(network))))
+            }
+            // Нейросеть необязательна
+            Err(NetworkError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => self.engine_data_errors.push(error.to_string()),
+        }
+        let tablebase_path =
+            Path::new(Self::TABLEBASE_DIR).join(Tablebase::default_file_name(variant));
+        match Tablebase::load(tablebase_path, variant.clone()) {
+            Ok(tablebase) => engine = engine.with_tablebase(Arc::new(tablebase)),
+            // Таблицы эндшпиля необязательны
+            Err(TablebaseError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => self.engine_data_errors.push(error.to_string()),
+        }
+        let book_path = Path::new(Self::BOOK_DIR).join(OpeningBook::default_file_name(variant));
+        match OpeningBook::load(book_path, variant.clone()) {
+            Ok(book) => engine = engine.with_book(Arc::new(book)),
+            // Дебютная книга необязательна
+            Err(OpeningBookError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => self.engine_data_errors.push(error.to_string()),
+        }
+        self.engine = engine.with_evaluator(self.selected_evaluator());
+    }
+
+    /// Возвращает оценку позиции, выбранную пользователем: нейросеть, если она включена
+    /// и загружена, иначе оценку по весам
+    fn selected_evaluator(&self) -> Arc<dyn Evaluator> {
+        match &self.network_evaluator {
+            Some(network_evaluator) if self.use_network => network_evaluator.clone(),
+            _ => self.handcrafted_evaluator.clone(),
+        }
+    }
+
+    /// Возвращает панель дебютной книги: ходы книги в текущей позиции со статистикой партий
+    fn book_view(&self) -> Element<'_, Message, Renderer<Theme>> {
+        let Some(book) = self.engine.book() else {
+            return Column::new().into();
+        };
+        let game_data = self.game_data.borrow();
+        let mut panel = column![text("Дебютная книга")].spacing(4);
+        let moves = book.moves(&game_data);
+        if moves.is_empty() {
+            panel = panel.push(text("Позиции нет в книге").size(14.0));
+        }
+        for book_move in moves {
+            panel = panel.push(
+                row![
+                    button(text(&book_move.notation).size(14.0))
+                        .on_press(Message::PlayBookMove(book_move.notation.clone())),
+                    text(format!(
+                        "{} партий: белые {:.0}%, ничьи {:.0}%, чёрные {:.0}%",
+                        book_move.games,
+                        book_move.percentage(GameResult::WhiteWins),
+                        book_move.percentage(GameResult::Draw),
+                        book_move.percentage(GameResult::BlackWins),
+                    ))
+                    .size(14.0),
+                ]
+                .spacing(6),
+            );
+        }
+        panel.into()
+    }
+
+    /// Начинает новую партию по правилам указанной разновидности игры
+    ///
+    /// Партия начинается из позиции FEN, если она указана. Иначе применяется фора, а без форы,
+    /// если включён дебют по жребию и для этой разновидности есть колода дебютов,
+    /// сразу делаются ходы случайного дебюта
+    fn start_game(&mut self, variant: Variant) -> Command<Message> {
+        self.set_editing(false);
+        self.board.set_hint(None);
+        self.new_game_error = None;
+        self.ballot = None;
+        let mut game_data = if self.fen.trim().is_empty() {
+            let mut game_data = GameData::new(variant);
+            if !self.handicap.is_empty() {
+                self.handicap.apply(&mut game_data);
+            } else if self.start_from_ballot {
+                self.ballot = BallotDeck::for_variant(&game_data.variant)
+                    .and_then(|deck| deck.ballots.choose(&mut rand::thread_rng()).cloned());
+            }
+            game_data
+        } else {
+            match GameData::from_fen(variant, &self.fen) {
+                Ok(game_data) => game_data,
+                Err(error) => {
+                    self.new_game_error = Some(error.to_string());
+                    return Command::none();
+                }
+            }
+        };
+        self.load_engine_data(&game_data.variant);
+        self.start_record(&game_data);
+        // Ходы дебюта записываются в партию
+        if let Some(ballot) = &self.ballot {
+            ballot.play(&mut game_data);
+            self.record.moves.extend(ballot.moves.iter().cloned());
+        }
+        self.game_data.replace(game_data);
+        self.board.update();
+        self.position_changed()
+    }
+
+    /// Начинает запись новой партии с указанной позиции
+    fn start_record(&mut self, game_data: &GameData) {
+        self.record = GameRecord::new(game_data.clone());
+        self.analysis.clear();
+        self.export_message = None;
+    }
+
+    /// Возвращает панель со списком ходов партии и их анализом
+    fn move_list_view(&self) -> Element<'_, Message, Renderer<Theme>> {
+        let mut moves = Column::new().spacing(2);
+        for (index, (notation, number)) in self
+            .record
+            .notation()
+            .into_iter()
+            .zip(self.record.move_numbers())
+            .enumerate()
+        {
+            let move_analysis = self.analysis.get(index);
+            let judgement = move_analysis.and_then(|move_analysis| move_analysis.judgement);
+            moves = moves.push(text(format!(
+                "{} {}{}",
+                number.unwrap_or_default(),
+                notation,
+                judgement
+                    .map(|judgement| judgement.symbol())
+                    .unwrap_or_default()
+            )));
+            if let (Some(move_analysis), Some(judgement)) = (move_analysis, judgement) {
+                moves = moves.push(
+                    text(move_analysis.comment(judgement))
+                        .size(14.0)
+                        .style(Self::JUDGEMENT_COLOR),
+                );
+            }
+        }
+
+        let analyse_button = button(text(if self.analysing {
+            "Анализ..."
+        } else {
+            "Анализ партии"
+        }));
+        let mut panel = column![row![
+            if self.analysing {
+                analyse_button
+            } else {
+                analyse_button.on_press(Message::Analyse)
+            },
+            button(text("Сохранить PDN")).on_press(Message::ExportPdn),
+        ]
+        .spacing(10),]
+        .spacing(10)
+        .padding(10)
+        .width(Length::Fixed(Self::MOVE_LIST_WIDTH));
+        if let Some(export_message) = &self.export_message {
+            panel = panel.push(text(export_message).size(14.0));
+        }
+        panel
+            .push(self.book_view())
+            .push(scrollable(moves).height(Length::Fill))
+            .into()
+    }
+
+    /// Включает или выключает редактор позиции
+    fn set_editing(&mut self, editing: bool) {
+        self.editing = editing;
+        self.stop_live_analysis();
+        self.board.set_hint(None);
+        self.editor_message = None;
+        self.board.set_editing(editing);
+        self.board.update();
+    }
+
+    /// Меняет фигуру в ячейке по кругу: пусто, белая шашка, белая дамка, чёрная шашка,
+    /// чёрная дамка и снова пусто
+    fn cycle_piece(&mut self, position: Position) {
+        let mut game_data = self.game_data.borrow_mut();
+        let current = game_data
+            .white_pieces
+            .remove(&position)
+            .map(|piece| (Side::White, piece))
+            .or_else(|| {
+                game_data
+                    .black_pieces
+                    .remove(&position)
+                    .map(|piece| (Side::Black, piece))
+            });
+        let next = match current {
+            None => Some((Side::White, Piece::Man)),
+            Some((Side::White, Piece::Man)) => Some((Side::White, Piece::King)),
+            Some((Side::White, Piece::King)) => Some((Side::Black, Piece::Man)),
+            Some((Side::Black, Piece::Man)) => Some((Side::Black, Piece::King)),
+            Some((Side::Black, Piece::King)) => None,
+        };
+        match next {
+            Some((Side::White, piece)) => {
+                game_data.white_pieces.insert(position, piece);
+            }
+            Some((Side::Black, piece)) => {
+                game_data.black_pieces.insert(position, piece);
+            }
+            None => {}
+        }
+    }
+
+    /// Проверяет расстановку в редакторе, возвращает описания найденных нарушений
+    fn validate_setup(game_data: &GameData) -> Vec<String> {
+        match game_data.validate_start() {
+            Ok(()) => Vec::new(),
+            Err(violations) => violations.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Запускает действия, которые зависят от позиции на доске: ход компьютера и её оценку
+    fn position_changed(&mut self) -> Command<Message> {
+        Command::batch([self.request_computer_move(), self.restart_live_analysis()])
+    }
+
+    /// Прерывает фоновую оценку позиции и убирает её с доски
+    fn stop_live_analysis(&mut self) {
+        self.live_analysis_stop.store(true, Ordering::Relaxed);
+        self.live_analysis_stop = Arc::new(AtomicBool::new(false));
+        self.board.set_evaluation(None);
+    }
+
+    /// Начинает оценку текущей позиции заново с глубины 1, если она включена
+    fn restart_live_analysis(&mut self) -> Command<Message> {
+        self.stop_live_analysis();
+        let game_data = self.game_data.borrow().clone();
+        if !self.live_analysis || self.editing || game_data.is_game_ended() {
+            return Command::none();
+        }
+        self.search_live(game_data, 1)
+    }
+
+    /// Перебирает позицию в фоне на указанную глубину
+    ///
+    /// После каждой глубины приходит сообщение EvaluationUpdated, по которому запускается
+    /// перебор на следующую глубину (итеративное углубление)
+    fn search_live(&self, game_data: GameData, depth: u32) -> Command<Message> {
+        let engine = self
+            .engine
+            .with_depth(depth)
+            .with_stop_flag(self.live_analysis_stop.clone());
+        Command::perform(
+            async move {
+                let result = engine.search(&game_data);
+                (game_data.to_fen(), result)
+            },
+            |(fen, result)| Message::EvaluationUpdated(fen, result),
+        )
+    }
+
+    /// Если сейчас ходит компьютер, запускает поиск его хода в фоне
+    ///
+    /// На время поиска пользователю запрещено делать ходы
+    fn request_computer_move(&mut self) -> Command<Message> {
+        let game_data = self.game_data.borrow().clone();
+        if self.editing
+            || self.computer_side != Some(game_data.current_move)
+            || game_data.is_game_ended()
+        {
+            self.board.set_interactive(true);
+            return Command::none();
         }
+        self.board.set_interactive(false);
+        let engine = self.engine.clone();
+        Command::perform(
+            async move { engine.best_move(&game_data) },
+            Message::ComputerMove,
+        )
     }
 }
 
@@ -50,7 +487,11 @@ impl Application for Checkers {
     type Flags = ();
 
     fn new(_flags: Self::Flags) -> (Self, Command<Self::Message>) {
-        (Self::default(), Command::none())
+        let mut checkers = Self::default();
+        checkers.load_variants();
+        let variant = checkers.game_data.borrow().variant.clone();
+        checkers.load_engine_data(&variant);
+        (checkers, Command::none())
     }
 
     fn title(&self) -> String {
@@ -61,49 +502,220 @@ impl Application for Checkers {
         match message {
             Message::Board(board_message) => {
                 match board_message {
-                    BoardMessage::MovePiece { from, to, side } => {
-                        let mut game_data = self.game_data.borrow_mut();
-                        game_data.move_piece(side, from, to);
-                        if game_data.is_turning_to_king_condition_satisfied(side, to) {
-                            game_data.turn_man_to_king(side, to);
-                            // После превращения шашки в дамку ход продолжается
-                        } else {
-                            game_data.pass_the_move();
-                        }
+                    BoardMessage::MakeMove { side, piece_move } => {
+                        self.board.set_hint(None);
+                        self.game_data.borrow_mut().make_move(side, &piece_move);
+                        // Окончившаяся партия остаётся на доске, чтобы её можно было проанализировать
+                        self.record.push(piece_move);
+                        self.analysis.clear();
+                        self.board.update();
                     }
-                    BoardMessage::TakePieces {
-                        from,
-                        to,
-                        side,
-                        taken_pieces_positions,
-                    } => {
-                        let mut game_data = self.game_data.borrow_mut();
-                        // Убираем все фишки на пути, так как они были "съедены" во время взятия
-                        game_data.remove_pieces(&taken_pieces_positions, side.opposite());
-                        game_data.move_piece(side, from, to);
-                        if game_data.is_turning_to_king_condition_satisfied(side, to) {
-                            game_data.turn_man_to_king(side, to);
-                            // После превращения шашки в дамку ход продолжается
-                        } else {
-                            game_data.pass_the_move();
-                        }
-                        if game_data.is_game_ended() {
-                            return Command::perform(std::future::ready(()), |_| {
-                                Message::Board(BoardMessage::Restart)
-                            });
-                        }
-                    }
-                    BoardMessage::Restart => {
-                        self.game_data.replace(GameData::default());
+                    BoardMessage::CyclePiece(position) => {
+                        self.cycle_piece(position);
+                        self.editor_message = None;
+                        self.board.update();
+                        return Command::none();
                     }
                 }
-                self.board.update();
+                return self.position_changed();
             }
             Message::EventOccured(event) => {
                 if let Event::Window(window::Event::CloseRequested) = event {
                     return window::close();
                 }
             }
+            Message::VariantSelected(variant) => {
+                self.selected_variant = variant;
+            }
+            Message::ReloadVariants => {
+                self.load_variants();
+            }
+            Message::NewGame => {
+                return self.start_game(self.selected_variant.clone());
+            }
+            Message::StartFromBallotToggled(enabled) => {
+                self.start_from_ballot = enabled;
+            }
+            Message::HandicapStrongerSideSelected(side) => {
+                self.handicap.stronger_side = side;
+            }
+            Message::HandicapRemovedMenSelected(removed_men) => {
+                self.handicap.removed_men = removed_men;
+            }
+            Message::HandicapKingToggled(enabled) => {
+                self.handicap.weaker_side_king = enabled;
+            }
+            Message::HandicapExtraMovesSelected(extra_moves) => {
+                self.handicap.extra_moves = extra_moves;
+            }
+            Message::FenChanged(fen) => {
+                self.fen = fen;
+            }
+            Message::ComputerToggled(enabled) => {
+                // Компьютер играет за сторону, которая сейчас не ходит, чтобы ход остался у пользователя
+                self.computer_side =
+                    enabled.then(|| self.game_data.borrow().current_move.opposite());
+                return self.request_computer_move();
+            }
+            Message::Hint => {
+                let game_data = self.game_data.borrow().clone();
+                let engine = self.engine.clone();
+                return Command::perform(
+                    async move {
+                        // В эндшпиле, который есть в таблицах, лучший ход известен точно
+                        let hint = match engine
+                            .tablebase()
+                            .and_then(|tablebase| tablebase.best_move(&game_data))
+                        {
+                            Some((piece_move, _)) => Some(piece_move),
+                            None => engine.best_move(&game_data),
+                        };
+                        (game_data, hint)
+                    },
+                    |(game_data, hint)| Message::HintFound(game_data.to_fen(), hint),
+                );
+            }
+            Message::HintFound(fen, hint) => {
+                // Подсказка устарела, если позиция изменилась, пока движок думал
+                if !self.editing && self.game_data.borrow().to_fen() == fen {
+                    self.board.set_hint(hint);
+                }
+            }
+            Message::NetworkEvaluationToggled(enabled) => {
+                self.use_network = enabled;
+                self.engine = self
+                    .engine
+                    .clone()
+                    .with_evaluator(self.selected_evaluator());
+                return self.restart_live_analysis();
+            }
+            Message::LiveAnalysisToggled(enabled) => {
+                self.live_analysis = enabled;
+                return self.restart_live_analysis();
+            }
+            Message::EvaluationUpdated(fen, result) => {
+                let game_data = self.game_data.borrow().clone();
+                // Оценка устарела, если позиция изменилась или анализ выключен, пока движок думал
+                if !result.completed
+                    || !self.live_analysis
+                    || self.editing
+                    || game_data.to_fen() != fen
+                {
+                    return Command::none();
+                }
+                self.board.set_evaluation(Some(Evaluation::new(
+                    game_data.current_move,
+                    result.score,
+                    result.depth,
+                    game_data.line_notation(&result.principal_variation),
+                )));
+                // Найденный выигрыш не изменится с увеличением глубины
+                if result.depth < Self::LIVE_ANALYSIS_MAX_DEPTH
+                    && !Engine::is_win_score(result.score)
+                {
+                    return self.search_live(game_data, result.depth + 1);
+                }
+            }
+            Message::PlayBookMove(notation) => {
+                let game_data = self.game_data.borrow();
+                let side = game_data.current_move;
+                // Пока ходит компьютер или включён редактор, ходы из книги не делаются
+                if let Some(piece_move) = game_data.parse_move(&notation).filter(|_| {
+                    !self.editing && self.computer_side != Some(side) && !game_data.is_game_ended()
+                }) {
+                    drop(game_data);
+                    return self
+                        .update(Message::Board(BoardMessage::MakeMove { side, piece_move }));
+                }
+            }
+            Message::Analyse => {
+                self.analysing = true;
+                let record = self.record.clone();
+                let engine = self.engine.clone();
+                return Command::perform(
+                    async move { engine.analyse(&record) },
+                    Message::AnalysisFinished,
+                );
+            }
+            Message::AnalysisFinished(analysis) => {
+                self.analysing = false;
+                // Анализ устарел, если за время анализа в партии появились новые ходы
+                if analysis
+                    .iter()
+                    .map(|move_analysis| &move_analysis.played_move)
+                    .eq(self.record.moves.iter())
+                {
+                    self.analysis = analysis;
+                }
+            }
+            Message::ExportPdn => {
+                self.export_message = Some(
+                    match fs::write(Self::PDN_FILE, self.record.to_pdn(&self.analysis)) {
+                        Ok(()) => format!("Партия сохранена в {}", Self::PDN_FILE),
+                        Err(error) => format!("Не удалось сохранить партию: {}", error),
+                    },
+                );
+            }
+            Message::EditPosition => {
+                self.ballot = None;
+                self.set_editing(true);
+            }
+            Message::ClearBoard => {
+                let mut game_data = self.game_data.borrow_mut();
+                game_data.white_pieces.clear();
+                game_data.black_pieces.clear();
+                drop(game_data);
+                self.editor_message = None;
+                self.board.update();
+            }
+            Message::ResetPosition => {
+                let variant = self.game_data.borrow().variant.clone();
+                self.game_data.replace(GameData::new(variant));
+                self.editor_message = None;
+                self.board.update();
+            }
+            Message::EditorSideSelected(side) => {
+                self.game_data.borrow_mut().current_move = side;
+                self.board.update();
+            }
+            Message::ValidatePosition => {
+                let errors = Self::validate_setup(&self.game_data.borrow());
+                self.editor_message = Some(if errors.is_empty() {
+                    String::from("Позиция корректна")
+                } else {
+                    errors.join("; ")
+                });
+            }
+            Message::PlayFromPosition => {
+                let errors = Self::validate_setup(&self.game_data.borrow());
+                if !errors.is_empty() {
+                    self.editor_message = Some(errors.join("; "));
+                    return Command::none();
+                }
+                let game_data = {
+                    let mut game_data = self.game_data.borrow_mut();
+                    game_data.consecutive_king_moves.clear();
+                    game_data.extra_moves = 0;
+                    game_data.clone()
+                };
+                self.start_record(&game_data);
+                self.set_editing(false);
+                return self.position_changed();
+            }
+            Message::ComputerMove(piece_move) => {
+                let game_data = self.game_data.borrow();
+                let side = game_data.current_move;
+                // Пока компьютер думал, могла начаться новая партия
+                if let Some(piece_move) = piece_move.filter(|piece_move| {
+                    !self.editing
+                        && self.computer_side == Some(side)
+                        && game_data.get_all_available_moves().contains(piece_move)
+                }) {
+                    drop(game_data);
+                    return self
+                        .update(Message::Board(BoardMessage::MakeMove { side, piece_move }));
+                }
+            }
         }
         Command::none()
     }
@@ -113,6 +725,127 @@ impl Application for Checkers {
     }
 
     fn view(&self) -> Element<'_, Self::Message, Renderer<Self::Theme>> {
-        column![self.board.view().map(Message::Board)].into()
+        let mut new_game_controls = row![
+            text("Правила:"),
+            pick_list(
+                &self.variants[..],
+                Some(self.selected_variant.clone()),
+                Message::VariantSelected
+            ),
+            button(text("Новая игра")).on_press(Message::NewGame),
+            button(text("Обновить правила")).on_press(Message::ReloadVariants),
+            button(text("Расставить фигуры")).on_press(Message::EditPosition),
+            button(text("Подсказка")).on_press(Message::Hint),
+            checkbox(
+                "Игра против компьютера",
+                self.computer_side.is_some(),
+                Message::ComputerToggled
+            ),
+            checkbox(
+                "Оценка позиции",
+                self.live_analysis,
+                Message::LiveAnalysisToggled
+            ),
+            checkbox(
+                "Дебют по жребию",
+                self.start_from_ballot,
+                Message::StartFromBallotToggled
+            ),
+        ]
+        .spacing(10)
+        .padding(10);
+        if self.network_evaluator.is_some() {
+            new_game_controls = new_game_controls.push(checkbox(
+                "Нейросетевая оценка",
+                self.use_network,
+                Message::NetworkEvaluationToggled,
+            ));
+        }
+
+        let start_position_controls = row![
+            text("Фору даёт:"),
+            pick_list(
+                Self::SIDES.to_vec(),
+                Some(self.handicap.stronger_side),
+                Message::HandicapStrongerSideSelected
+            ),
+            text("Убрать шашек:"),
+            pick_list(
+                (0..=Self::MAX_HANDICAP_MEN).collect::<Vec<u8>>(),
+                Some(self.handicap.removed_men),
+                Message::HandicapRemovedMenSelected
+            ),
+            checkbox(
+                "Дамка у слабого",
+                self.handicap.weaker_side_king,
+                Message::HandicapKingToggled
+            ),
+            text("Лишних ходов:"),
+            pick_list(
+                (0..=Self::MAX_HANDICAP_MOVES).collect::<Vec<u8>>(),
+                Some(self.handicap.extra_moves),
+                Message::HandicapExtraMovesSelected
+            ),
+            text("FEN:"),
+            text_input("W:W21-32:B1-12", &self.fen)
+                .on_input(Message::FenChanged)
+                .width(Length::Fixed(300.0)),
+        ]
+        .spacing(10)
+        .padding([0, 10, 10, 10]);
+
+        let mut content = column![new_game_controls];
+        if self.editing {
+            let editor_controls = row![
+                button(text("Очистить")).on_press(Message::ClearBoard),
+                button(text("Начальная позиция")).on_press(Message::ResetPosition),
+                text("Ходят:"),
+                pick_list(
+                    Self::SIDES.to_vec(),
+                    Some(self.game_data.borrow().current_move),
+                    Message::EditorSideSelected
+                ),
+                button(text("Проверить")).on_press(Message::ValidatePosition),
+                button(text("Играть с этой позиции")).on_press(Message::PlayFromPosition),
+            ]
+            .spacing(10)
+            .padding([0, 10, 10, 10]);
+            content = content.push(editor_controls);
+            if let Some(editor_message) = &self.editor_message {
+                content = content.push(text(editor_message).width(Length::Fill));
+            }
+        } else {
+            content = content.push(start_position_controls);
+        }
+        if let Some(error) = &self.new_game_error {
+            content = content.push(
+                text(error)
+                    .style(Color::from_rgb(0.8, 0.0, 0.0))
+                    .width(Length::Fill),
+            );
+        }
+        if let Some(ballot) = &self.ballot {
+            content = content.push(text(format!("Дебют: {}", ballot)).width(Length::Fill));
+        }
+        for error in &self.engine_data_errors {
+            content = content.push(
+                text(error)
+                    .style(Color::from_rgb(0.8, 0.0, 0.0))
+                    .width(Length::Fill),
+            );
+        }
+        if let Some(error) = &self.custom_variants_error {
+            content = content.push(
+                text(error)
+                    .style(Color::from_rgb(0.8, 0.0, 0.0))
+                    .width(Length::Fill),
+            );
+        }
+        content
+            .push(row![
+                self.board.view().map(Message::Board),
+                self.move_list_view()
+            ])
+            .into()
     }
 }
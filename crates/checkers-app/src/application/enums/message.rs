@@ -16,10 +16,67 @@ limitations under the License.
 
 use iced::event;
 
+use checkers_lib::{
+    enums::{Side, Variant},
+    structs::{Move, MoveAnalysis, SearchResult},
+};
+
 use crate::application::structs::BoardMessage;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Board(BoardMessage),
     EventOccured(event::Event),
+    /// Пользователь выбрал разновидность игры для новой партии
+    VariantSelected(Variant),
+    /// Перечитать файл с пользовательскими разновидностями игры
+    ReloadVariants,
+    /// Начать новую партию по правилам выбранной разновидности игры
+    NewGame,
+    /// Пользователь включил или выключил начало партии с дебюта по жребию
+    StartFromBallotToggled(bool),
+    /// Пользователь выбрал сторону, которая даёт фору
+    HandicapStrongerSideSelected(Side),
+    /// Пользователь выбрал, сколько шашек убрать у стороны, которая даёт фору
+    HandicapRemovedMenSelected(u8),
+    /// Пользователь включил или выключил дамку у стороны, которая получает фору
+    HandicapKingToggled(bool),
+    /// Пользователь выбрал, сколько лишних ходов получает сторона, которая получает фору
+    HandicapExtraMovesSelected(u8),
+    /// Пользователь изменил начальную позицию в формате FEN
+    FenChanged(String),
+    /// Показать ход, который движок советует стороне, которая сейчас ходит
+    Hint,
+    /// Движок нашёл подсказку для позиции, записанной в формате FEN. None - если ходов нет
+    HintFound(String, Option<Move>),
+    /// Пользователь переключил оценку позиции движком между нейросетью и оценкой по весам
+    NetworkEvaluationToggled(bool),
+    /// Пользователь включил или выключил оценку текущей позиции движком
+    LiveAnalysisToggled(bool),
+    /// Движок закончил перебор позиции, записанной в формате FEN, на очередную глубину
+    EvaluationUpdated(String, SearchResult),
+    /// Сделать ход из дебютной книги, записанный в числовой нотации
+    PlayBookMove(String),
+    /// Проанализировать ходы текущей партии
+    Analyse,
+    /// Анализ партии завершён
+    AnalysisFinished(Vec<MoveAnalysis>),
+    /// Сохранить партию в формате PDN
+    ExportPdn,
+    /// Включить редактор позиции
+    EditPosition,
+    /// Убрать все фигуры с доски в редакторе позиции
+    ClearBoard,
+    /// Вернуть начальную позицию в редакторе позиции
+    ResetPosition,
+    /// Пользователь выбрал в редакторе позиции сторону, которая ходит
+    EditorSideSelected(Side),
+    /// Проверить позицию в редакторе
+    ValidatePosition,
+    /// Выйти из редактора и начать игру с расставленной позиции
+    PlayFromPosition,
+    /// Пользователь включил или выключил игру против компьютера
+    ComputerToggled(bool),
+    /// Компьютер выбрал ход. None - если ходов нет
+    ComputerMove(Option<Move>),
 }
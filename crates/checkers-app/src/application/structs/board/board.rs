@@ -25,14 +25,11 @@ use iced::{
 use once_cell::sync::Lazy;
 
 use checkers_lib::{
-    enums::{Piece, Route, Side},
-    structs::Position,
+    enums::{Piece, Side},
+    structs::{GameData, Move, Position},
 };
 
-use crate::application::structs::{
-    board::{Message, State},
-    GameData,
-};
+use crate::application::structs::board::{Evaluation, Message, State};
 
 // Преднастроенная конфигурация текстового элемента, отображаемого в overlay
 static OVERLAY_TEXT_PRESET: Lazy<Text> = Lazy::new(|| Text {
@@ -51,6 +48,14 @@ pub struct Board {
     board_cache: Cache,
     /// Хранит сгенерированные примитивы для отрисовки фигур игровой доски
     pieces_cache: Cache,
+    /// Может ли пользователь делать ходы. Ходы запрещены, пока ходит компьютер
+    interactive: bool,
+    /// Включён ли режим редактора позиции, в котором фигуры расставляются нажатием на ячейки
+    editing: bool,
+    /// Ход, который движок советует сделать стороне, которая сейчас ходит
+    hint: Option<Move>,
+    /// Оценка текущей позиции движком. None - если позиция не анализируется
+    evaluation: Option<Evaluation>,
 }
 
 impl Board {
@@ -58,11 +63,21 @@ impl Board {
     const GRAY_CELL_COLOR: Color = Color::from_rgb(0.75, 0.75, 0.75);
     /// Цвет "черных" ячеек доски
     const RED_CELL_COLOR: Color = Color::from_rgb(0.644, 0.164, 0.164);
+    /// Цвет ячеек одноцветной доски, на которой используются все клетки
+    const UNIFORM_CELL_COLOR: Color = Color::from_rgb(0.871, 0.722, 0.529);
+    /// Цвет линий, разделяющих ячейки одноцветной доски
+    const GRID_COLOR: Color = Color::from_rgb(0.4, 0.26, 0.13);
+    /// Половина толщины линий, разделяющих ячейки одноцветной доски (в долях ячейки)
+    const GRID_LINE_WIDTH: f32 = 0.01;
     /// Цвет ячейки, над которой находится курсор пользователя
     const HOVERED_CELL_COLOR: Color = Color::from_rgba(0.574, 0.437, 0.855, 0.42);
 
     const AVAILABLE_CELL_FOR_MOVING_COLOR: Color = Color::from_rgba(0.0, 1.0, 0.0, 1.0);
     const AVAILABLE_CELL_FOR_TAKING_COLOR: Color = Color::from_rgba(1.0, 0.0, 0.0, 1.0);
+    /// Цвет ячеек подсказки: начальная и конечная позиции хода, который советует движок
+    const HINT_CELL_COLOR: Color = Color::from_rgba(0.0, 0.6, 1.0, 0.6);
+    /// Цвет ячеек, по которым можно уточнить одно из нескольких взятий
+    const DISTINCTIVE_CELL_FOR_TAKING_COLOR: Color = Color::from_rgba(1.0, 0.5, 0.0, 0.7);
 
     const BLACK_PIECE_COLOR: Color = Color::BLACK;
     const BLACK_PIECE_MOVING_COLOR: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.42);
@@ -70,40 +85,86 @@ impl Board {
     const WHITE_PIECE_MOVING_COLOR: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.42);
     const KING_CROWN_COLOR: Color = Color::from_rgb(0.996, 0.839, 0.0);
 
-    /// Размер ячейки доски
-    const CELL_WIDTH: f32 = 80.0;
+    /// Наибольший размер ячейки доски
+    ///
+    /// Если доска с ячейками такого размера не помещается в окно, ячейки уменьшаются
+    const MAX_CELL_WIDTH: f32 = 80.0;
     /// Радиус фигуры
     const PIECE_RADIUS: f32 = 0.4;
     /// Радиус короны "дамки"
     const KING_CROWN_RADIUS: f32 = 0.2;
 
     const BOARD_MARGIN_RIGHT: f32 = 10.0;
+    /// Ширина области с текстовой информацией справа от доски
+    const INFO_PANEL_WIDTH: f32 = 450.0;
     const SPACING_BETWEEN_TEXT: f32 = 20.0;
+    /// Ширина шкалы оценки позиции между доской и текстовой информацией
+    const EVALUATION_BAR_WIDTH: f32 = 20.0;
+    /// Количество ходов главного варианта в одной строке
+    const PRINCIPAL_VARIATION_MOVES_PER_LINE: usize = 8;
 
     pub fn new(game_data: Rc<RefCell<GameData>>) -> Self {
         Self {
             game_data,
             board_cache: Cache::new(),
             pieces_cache: Cache::new(),
+            interactive: true,
+            editing: false,
+            hint: None,
+            evaluation: None,
         }
     }
 
+    /// Разрешает или запрещает пользователю делать ходы
+    pub fn set_interactive(&mut self, interactive: bool) {
+        self.interactive = interactive;
+    }
+
     pub fn view(&self) -> Element<Message> {
+        // Размер ячеек доски подбирается по доступному месту, см. get_cell_width
         Canvas::new(self)
             .width(Length::Fill)
             .height(Length::Fill)
             .into()
     }
 
+    /// Включает или выключает режим редактора позиции
+    pub fn set_editing(&mut self, editing: bool) {
+        self.editing = editing;
+    }
+
+    /// Показывает подсказку (ход, который советует движок) или убирает её
+    pub fn set_hint(&mut self, hint: Option<Move>) {
+        self.hint = hint;
+    }
+
+    /// Показывает оценку позиции движком или убирает её
+    pub fn set_evaluation(&mut self, evaluation: Option<Evaluation>) {
+        self.evaluation = evaluation;
+    }
+
     /// Ставит игровую доску в очередь для перерисовки
     pub fn update(&self) {
         self.board_cache.clear();
         self.pieces_cache.clear();
     }
 
-    fn get_board_size(&self) -> (f32, f32) {
+    /// Возвращает размер ячейки доски, при котором доска и текстовая информация помещаются
+    /// в области отрисовки
+    fn get_cell_width(&self, bounds: Rectangle) -> f32 {
+        let (rows, columns) = self.game_data.borrow().board_cells();
+        let available_width = bounds.width
+            - 2.0 * Self::BOARD_MARGIN_RIGHT
+            - Self::EVALUATION_BAR_WIDTH
+            - Self::INFO_PANEL_WIDTH;
+        (available_width / columns as f32)
+            .min(bounds.height / rows as f32)
+            .clamp(1.0, Self::MAX_CELL_WIDTH)
+    }
+
+    fn get_board_size(&self, bounds: Rectangle) -> (f32, f32) {
         let cells = self.game_data.borrow().board_cells();
-        let cell_width = Self::CELL_WIDTH;
+        let cell_width = self.get_cell_width(bounds);
         (cells.0 as f32 * cell_width, cells.1 as f32 * cell_width)
     }
 
@@ -111,10 +172,10 @@ impl Board {
     /// Возвращает позицию текстового элемента на данной строке
     ///
     /// Параметр row начинается с 0
-    fn get_text_line_point(&self, row: usize) -> Point {
-        let board_size = self.get_board_size();
+    fn get_text_line_point(&self, row: usize, bounds: Rectangle) -> Point {
+        let board_size = self.get_board_size(bounds);
         Point {
-            x: board_size.1 + Self::BOARD_MARGIN_RIGHT,
+            x: board_size.1 + 2.0 * Self::BOARD_MARGIN_RIGHT + Self::EVALUATION_BAR_WIDTH,
             y: row as f32 * Self::SPACING_BETWEEN_TEXT,
         }
     }
@@ -137,11 +198,47 @@ impl Board {
         }
     }
 
+    /// Рисует шкалу оценки справа от доски: белая часть снизу растёт, когда позиция лучше для белых
+    fn draw_evaluation_bar(frame: &mut Frame, evaluation: &Evaluation, board_size: (f32, f32)) {
+        let (height, width) = board_size;
+        let left = width + Self::BOARD_MARGIN_RIGHT;
+        frame.fill_rectangle(
+            Point::new(left, 0.0),
+            Size::new(Self::EVALUATION_BAR_WIDTH, height),
+            Self::BLACK_PIECE_COLOR,
+        );
+        let white_height = height * evaluation.white_share();
+        frame.fill_rectangle(
+            Point::new(left, height - white_height),
+            Size::new(Self::EVALUATION_BAR_WIDTH, white_height),
+            Self::WHITE_PIECE_COLOR,
+        );
+    }
+
+    /// Возвращает строки с оценкой позиции и главным вариантом
+    fn get_evaluation_str(evaluation: &Evaluation) -> String {
+        let mut lines = vec![
+            format!(
+                "Оценка: {} (глубина {})",
+                evaluation.score_str(),
+                evaluation.depth
+            ),
+            String::from("Главный вариант:"),
+        ];
+        lines.extend(
+            evaluation
+                .principal_variation
+                .chunks(Self::PRINCIPAL_VARIATION_MOVES_PER_LINE)
+                .map(|moves| moves.join(" ")),
+        );
+        lines.join("\n")
+    }
+
     /// Возвращает позицию ячейки игральной доски, которая содержит в себе данную точку
-    fn get_cell_position(point: Point) -> Position {
+    fn get_cell_position(point: Point, cell_width: f32) -> Position {
         Position {
-            row: (point.y / Self::CELL_WIDTH) as i8,
-            column: (point.x / Self::CELL_WIDTH) as i8,
+            row: (point.y / cell_width) as i8,
+            column: (point.x / cell_width) as i8,
         }
     }
 
@@ -174,6 +271,40 @@ impl Board {
         )
     }
 
+    /// Возвращает позиции, нажатие на которые однозначно определяет одно из взятий
+    ///
+    /// Такими позициями являются взятые фигуры и промежуточные ячейки, встречающиеся только в одном взятии
+    fn get_distinctive_positions(moves: &[Move]) -> Vec<Position> {
+        use itertools::Itertools;
+        moves
+            .iter()
+            .flat_map(Self::get_move_intermediate_positions)
+            .unique()
+            .filter(|position| Self::find_move_by_distinctive_position(moves, *position).is_some())
+            .collect()
+    }
+
+    /// Возвращает взятие, которое однозначно определяется указанной позицией
+    fn find_move_by_distinctive_position(moves: &[Move], position: Position) -> Option<Move> {
+        let mut matching_moves = moves.iter().filter(|piece_move| {
+            Self::get_move_intermediate_positions(piece_move).contains(&position)
+        });
+        match (matching_moves.next(), matching_moves.next()) {
+            (Some(piece_move), None) => Some(piece_move.clone()),
+            _ => None,
+        }
+    }
+
+    /// Возвращает взятые фигуры и промежуточные ячейки пути (без конечной)
+    fn get_move_intermediate_positions(piece_move: &Move) -> Vec<Position> {
+        piece_move
+            .taken_pieces_positions
+            .iter()
+            .chain(piece_move.path[..piece_move.path.len() - 1].iter())
+            .copied()
+            .collect()
+    }
+
     fn get_piece_at_position(&self, position: Position) -> Option<Piece> {
         let game_data = self.game_data.borrow();
         match game_data.current_move {
@@ -197,23 +328,55 @@ impl Program<Message> for Board {
         cursor: Cursor,
     ) -> Vec<Geometry> {
         let game_data = self.game_data.borrow();
+        let cell_width = self.get_cell_width(bounds);
         let board = self.board_cache.draw(renderer, bounds.size(), |frame| {
             frame.with_save(|frame| {
-                frame.scale(Self::CELL_WIDTH);
-                for (row, column) in game_data.board_cell_coordinates() {
-                    let color = if (row + column) % 2 == 0 {
-                        Self::GRAY_CELL_COLOR
-                    } else {
-                        Self::RED_CELL_COLOR
-                    };
-                    frame.fill_rectangle(Point::new(row as f32, column as f32), Size::UNIT, color);
+                frame.scale(cell_width);
+                let ruleset = game_data.ruleset();
+                // Если используются все клетки (например, в турецких шашках), доска одноцветная
+                if game_data
+                    .board_cell_coordinates()
+                    .all(|cell| ruleset.is_playable_cell(Position::from(cell)))
+                {
+                    let (rows, columns) = game_data.board_cells();
+                    frame.fill_rectangle(
+                        Point::ORIGIN,
+                        Size::new(columns as f32, rows as f32),
+                        Self::GRID_COLOR,
+                    );
+                    for (row, column) in game_data.board_cell_coordinates() {
+                        frame.fill_rectangle(
+                            Point::new(
+                                column as f32 + Self::GRID_LINE_WIDTH,
+                                row as f32 + Self::GRID_LINE_WIDTH,
+                            ),
+                            Size::new(
+                                1.0 - 2.0 * Self::GRID_LINE_WIDTH,
+                                1.0 - 2.0 * Self::GRID_LINE_WIDTH,
+                            ),
+                            Self::UNIFORM_CELL_COLOR,
+                        );
+                    }
+                } else {
+                    for (row, column) in game_data.board_cell_coordinates() {
+                        let color = if ruleset.is_playable_cell(Position::from((row, column))) {
+                            Self::RED_CELL_COLOR
+                        } else {
+                            Self::GRAY_CELL_COLOR
+                        };
+                        frame.fill_rectangle(
+                            Point::new(column as f32, row as f32),
+                            Size::UNIT,
+                            color,
+                        );
+                    }
                 }
             });
         });
 
         let pieces = self.pieces_cache.draw(renderer, bounds.size(), |frame| {
             frame.with_save(|frame| {
-                frame.scale(Self::CELL_WIDTH);
+                frame.scale(cell_width);
 
                 for (position, piece) in &game_data.black_pieces {
                     Self::draw_piece(frame, *position, *piece, &Self::BLACK_PIECE_COLOR);
@@ -227,12 +390,28 @@ impl Program<Message> for Board {
 
         let overlay = {
             let mut frame = Frame::new(renderer, bounds.size());
-            if let Some(position) = cursor.position_in(bounds).map(Self::get_cell_position) {
+            // Подсвечиваем фигуру, которой советует ходить движок, и её конечную ячейку
+            if let Some(hint) = &self.hint {
+                frame.with_save(|frame| {
+                    frame.scale(cell_width);
+                    for position in [hint.from, hint.to()] {
+                        frame.fill_rectangle(
+                            Point::new(position.column as f32, position.row as f32),
+                            Size::UNIT,
+                            Self::HINT_CELL_COLOR,
+                        );
+                    }
+                });
+            }
+            if let Some(position) = cursor
+                .position_in(bounds)
+                .map(|point| Self::get_cell_position(point, cell_width))
+            {
                 // Если пользователь указывает на одну из ячеек игральной доски
                 if game_data.is_inside_board(position) {
                     // Подсвечиваем ячейку доски, над которой находится курсор пользователя
                     frame.with_save(|frame| {
-                        frame.scale(Self::CELL_WIDTH);
+                        frame.scale(cell_width);
                         frame.fill_rectangle(
                             Point::new(position.column as f32, position.row as f32),
                             Size::UNIT,
@@ -257,61 +436,126 @@ impl Program<Message> for Board {
                                     Size::UNIT,
                                     Self::HOVERED_CELL_COLOR,
                                 );
-                                let available_routes =
-                                    game_data.get_available_routes(*initial_position, *piece);
+                                let available_moves =
+                                    game_data.get_available_moves(*initial_position);
 
                                 // Отрисовываем возможные ходы для даной фигуры
-                                for route in &available_routes {
-                                    match route {
-                                        Route::Movement(position) => {
-                                            frame.fill_rectangle(
-                                                Point::new(
-                                                    position.column as f32,
-                                                    position.row as f32,
-                                                ),
-                                                Size::UNIT,
-                                                Self::AVAILABLE_CELL_FOR_MOVING_COLOR,
-                                            );
-                                        }
-                                        Route::Taking(position, ..) => {
-                                            frame.fill_rectangle(
-                                                Point::new(
-                                                    position.column as f32,
-                                                    position.row as f32,
-                                                ),
-                                                Size::UNIT,
-                                                Self::AVAILABLE_CELL_FOR_TAKING_COLOR,
-                                            );
-                                        }
-                                    };
+                                for available_move in &available_moves {
+                                    let position = available_move.to();
+                                    frame.fill_rectangle(
+                                        Point::new(position.column as f32, position.row as f32),
+                                        Size::UNIT,
+                                        if available_move.is_taking() {
+                                            Self::AVAILABLE_CELL_FOR_TAKING_COLOR
+                                        } else {
+                                            Self::AVAILABLE_CELL_FOR_MOVING_COLOR
+                                        },
+                                    );
                                 }
 
-                                if game_data
-                                    .get_route_containing_position(&available_routes, position)
-                                    .is_some()
+                                if !game_data
+                                    .get_moves_ending_at_position(&available_moves, position)
+                                    .is_empty()
                                 {
                                     Self::draw_piece(frame, position, *piece, &moving_piece_color);
                                 }
                             }
-                            _ => {}
+                            State::ChoosingMove {
+                                piece,
+                                initial_position,
+                                moves,
+                            } => {
+                                let moving_piece_color = match game_data.current_move {
+                                    Side::White => Self::WHITE_PIECE_MOVING_COLOR,
+                                    Side::Black => Self::BLACK_PIECE_MOVING_COLOR,
+                                };
+                                frame.fill_rectangle(
+                                    Point::new(
+                                        initial_position.column as f32,
+                                        initial_position.row as f32,
+                                    ),
+                                    Size::UNIT,
+                                    Self::HOVERED_CELL_COLOR,
+                                );
+                                // Подсвечиваем ячейки, по которым можно выбрать взятие
+                                for distinctive_position in Self::get_distinctive_positions(moves) {
+                                    frame.fill_rectangle(
+                                        Point::new(
+                                            distinctive_position.column as f32,
+                                            distinctive_position.row as f32,
+                                        ),
+                                        Size::UNIT,
+                                        Self::DISTINCTIVE_CELL_FOR_TAKING_COLOR,
+                                    );
+                                }
+                                // Все взятия заканчиваются в одной ячейке
+                                if let Some(piece_move) = moves.first() {
+                                    Self::draw_piece(
+                                        frame,
+                                        piece_move.to(),
+                                        *piece,
+                                        &moving_piece_color,
+                                    );
+                                }
+                            }
+                            State::None | State::EditingPosition => {}
                         }
                     });
 
                     frame.fill_text(Text {
                         content: format!("Текущая ячейка: {}", position),
-                        position: self.get_text_line_point(0),
+                        position: self.get_text_line_point(0, bounds),
                         ..OVERLAY_TEXT_PRESET.clone()
                     });
                 }
             }
+            if let State::ChoosingMove { .. } = state {
+                frame.fill_text(Text {
+                    content: String::from("Выберите взятую фигуру, чтобы уточнить взятие"),
+                    position: self.get_text_line_point(9, bounds),
+                    ..OVERLAY_TEXT_PRESET.clone()
+                });
+            }
+            if game_data.is_game_ended() {
+                frame.fill_text(Text {
+                    content: match game_data.winner() {
+                        Some(winner) => format!("Партия окончена. Победили: {}", winner),
+                        None => String::from("Партия окончена"),
+                    },
+                    position: self.get_text_line_point(8, bounds),
+                    ..OVERLAY_TEXT_PRESET.clone()
+                });
+            }
+            if self.editing {
+                frame.fill_text(Text {
+                    content: String::from("Расстановка: нажмите на ячейку, чтобы сменить фигуру"),
+                    position: self.get_text_line_point(9, bounds),
+                    ..OVERLAY_TEXT_PRESET.clone()
+                });
+            }
+            if !self.interactive {
+                frame.fill_text(Text {
+                    content: String::from("Компьютер думает..."),
+                    position: self.get_text_line_point(10, bounds),
+                    ..OVERLAY_TEXT_PRESET.clone()
+                });
+            }
+            if let Some(evaluation) = &self.evaluation {
+                Self::draw_evaluation_bar(&mut frame, evaluation, self.get_board_size(bounds));
+                frame.fill_text(Text {
+                    content: Self::get_evaluation_str(evaluation),
+                    position: self.get_text_line_point(11, bounds),
+                    ..OVERLAY_TEXT_PRESET.clone()
+                });
+            }
             frame.fill_text(Text {
                 content: format!("Сейчас ходят: {}", game_data.current_move),
-                position: self.get_text_line_point(1),
+                position: self.get_text_line_point(1, bounds),
                 ..OVERLAY_TEXT_PRESET.clone()
             });
             frame.fill_text(Text {
                 content: self.get_stats_str(),
-                position: self.get_text_line_point(2),
+                position: self.get_text_line_point(2, bounds),
                 ..OVERLAY_TEXT_PRESET.clone()
             });
 
@@ -331,6 +575,29 @@ impl Program<Message> for Board {
         use event::Status;
         use mouse::Event::*;
         use Event::*;
+        if self.editing {
+            *state = State::EditingPosition;
+            if let (Mouse(ButtonPressed(Button::Left)), Some(cursor_position)) =
+                (event, cursor.position_in(bounds))
+            {
+                let position =
+                    Self::get_cell_position(cursor_position, self.get_cell_width(bounds));
+                let game_data = self.game_data.borrow();
+                if game_data.is_inside_board(position)
+                    && game_data.ruleset().is_playable_cell(position)
+                {
+                    return (Status::Captured, Some(Message::CyclePiece(position)));
+                }
+            }
+            return (Status::Ignored, None);
+        }
+        if let State::EditingPosition = state {
+            *state = State::None;
+        }
+        if !self.interactive {
+            *state = State::None;
+            return (Status::Ignored, None);
+        }
         if let Mouse(ButtonPressed(Button::Right)) = event {
             *state = State::None;
             return (Status::Captured, None);
@@ -338,11 +605,12 @@ impl Program<Message> for Board {
 
         // Выбор фишки для перемещения или выбор позиции, в которую переместить фишку
         if let Some(cursor_position) = cursor.position_in(bounds) {
+            let cell_width = self.get_cell_width(bounds);
             let game_data = self.game_data.borrow();
             if let Mouse(ButtonPressed(Button::Left)) = event {
-                match *state {
+                match state {
                     State::None => {
-                        let initial_position = Self::get_cell_position(cursor_position);
+                        let initial_position = Self::get_cell_position(cursor_position, cell_width);
                         if let Some(piece) = self.get_piece_at_position(initial_position) {
                             *state = State::MovingPiece {
                                 initial_position,
@@ -354,37 +622,50 @@ impl Program<Message> for Board {
                         initial_position,
                         piece,
                     } => {
-                        let result_position = Self::get_cell_position(cursor_position);
-                        let available_routes =
-                            game_data.get_available_routes(initial_position, piece);
+                        let (initial_position, piece) = (*initial_position, *piece);
+                        let result_position = Self::get_cell_position(cursor_position, cell_width);
+                        let available_moves = game_data.get_available_moves(initial_position);
+                        let mut moves = game_data
+                            .get_moves_ending_at_position(&available_moves, result_position);
                         // Если пользователь совершает перемещение в корректную ячейку
-                        if let Some(route) = game_data
-                            .get_route_containing_position(&available_routes, result_position)
+                        match moves.len() {
+                            0 => {}
+                            1 => {
+                                *state = State::None;
+                                return (
+                                    Status::Captured,
+                                    Some(Message::MakeMove {
+                                        side: game_data.current_move,
+                                        piece_move: moves.remove(0),
+                                    }),
+                                );
+                            }
+                            // Несколько различных взятий заканчиваются в этой ячейке,
+                            // пользователь должен уточнить, какое из них он совершает
+                            _ => {
+                                *state = State::ChoosingMove {
+                                    initial_position,
+                                    piece,
+                                    moves,
+                                };
+                                return (Status::Captured, None);
+                            }
+                        }
+                    }
+                    State::EditingPosition => {}
+                    State::ChoosingMove { moves, .. } => {
+                        let clicked_position = Self::get_cell_position(cursor_position, cell_width);
+                        if let Some(piece_move) =
+                            Self::find_move_by_distinctive_position(moves, clicked_position)
                         {
                             *state = State::None;
-                            match route {
-                                Route::Movement(_position) => {
-                                    return (
-                                        Status::Captured,
-                                        Some(Message::MovePiece {
-                                            from: initial_position,
-                                            to: result_position,
-                                            side: game_data.current_move,
-                                        }),
-                                    );
-                                }
-                                Route::Taking(_position, taken_pieces_positions) => {
-                                    return (
-                                        Status::Captured,
-                                        Some(Message::TakePieces {
-                                            from: initial_position,
-                                            to: result_position,
-                                            side: game_data.current_move,
-                                            taken_pieces_positions,
-                                        }),
-                                    )
-                                }
-                            }
+                            return (
+                                Status::Captured,
+                                Some(Message::MakeMove {
+                                    side: game_data.current_move,
+                                    piece_move,
+                                }),
+                            );
                         }
                     }
                 }
@@ -0,0 +1,68 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{enums::Side, structs::Engine};
+
+/// Оценка позиции движком, отображаемая рядом с доской
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    /// Оценка с точки зрения белых
+    pub white_score: i32,
+    /// Глубина перебора, на которой получена оценка
+    pub depth: u32,
+    /// Главный вариант в числовой нотации
+    pub principal_variation: Vec<String>,
+}
+
+impl Evaluation {
+    /// Оценка, при которой шкала полностью заполняется цветом одной из сторон
+    const BAR_LIMIT: f32 = 1000.0;
+
+    /// Создаёт оценку по оценке с точки зрения стороны, которая ходит
+    pub fn new(side: Side, score: i32, depth: u32, principal_variation: Vec<String>) -> Self {
+        Self {
+            white_score: match side {
+                Side::White => score,
+                Side::Black => -score,
+            },
+            depth,
+            principal_variation,
+        }
+    }
+
+    /// Доля шкалы оценки, занятая белыми, от 0 до 1
+    pub fn white_share(&self) -> f32 {
+        if Engine::is_win_score(self.white_score) {
+            return if self.white_score > 0 { 1.0 } else { 0.0 };
+        }
+        0.5 + 0.5 * (self.white_score as f32 / Self::BAR_LIMIT).clamp(-1.0, 1.0)
+    }
+
+    /// Оценка в шашках со знаком или сведения о найденном выигрыше
+    pub fn score_str(&self) -> String {
+        if Engine::is_win_score(self.white_score) {
+            let plies = Engine::WIN_SCORE - self.white_score.abs();
+            let winner = if self.white_score > 0 {
+                Side::White
+            } else {
+                Side::Black
+            };
+            format!("выигрыш ({}), полуходов до конца: {}", winner, plies)
+        } else {
+            format!("{:+.2}", self.white_score as f32 / 100.0)
+        }
+    }
+}
@@ -14,23 +14,16 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use checkers_lib::{enums::Side, structs::Position};
+use checkers_lib::{
+    enums::Side,
+    structs::{Move, Position},
+};
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    // Переместить фигуру из позиции from в позицию to
-    MovePiece {
-        from: Position,
-        to: Position,
-        side: Side,
-    },
-    /// Взятие фигур фигурой, перемещая её из позиции from в позицию to
-    TakePieces {
-        from: Position,
-        to: Position,
-        side: Side,
-        /// Позиции взятых фигур
-        taken_pieces_positions: Vec<Position>,
-    },
-    Restart,
+    /// Совершить ход (перемещение или взятие фигур) за указанную сторону
+    MakeMove { side: Side, piece_move: Move },
+    /// Сменить фигуру в ячейке в режиме редактора позиции: пусто, белая шашка, белая дамка,
+    /// чёрная шашка, чёрная дамка и снова пусто
+    CyclePiece(Position),
 }
@@ -1,5 +1,6 @@
 mod board;
+mod evaluation;
 mod message;
 mod state;
 
-pub use self::{board::Board, message::Message, state::State};
+pub use self::{board::Board, evaluation::Evaluation, message::Message, state::State};
@@ -13,7 +13,10 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
-use checkers_lib::{enums::Piece, structs::Position};
+use checkers_lib::{
+    enums::Piece,
+    structs::{Move, Position},
+};
 
 #[derive(Default)]
 pub enum State {
@@ -25,4 +28,15 @@ pub enum State {
         initial_position: Position,
         piece: Piece,
     },
+    /// Пользователь выбирает одно из нескольких взятий, которые заканчиваются в одной ячейке
+    ///
+    /// Взятие выбирается нажатием на одну из фигур противника (или промежуточную ячейку),
+    /// которая встречается только в одном из возможных взятий
+    ChoosingMove {
+        initial_position: Position,
+        piece: Piece,
+        moves: Vec<Move>,
+    },
+    /// Пользователь расставляет фигуры на доске в режиме редактора позиции
+    EditingPosition,
 }
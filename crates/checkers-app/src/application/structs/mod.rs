@@ -1,7 +1,3 @@
 pub mod board;
-mod game_data;
 
-pub use self::{
-    board::{Board, Message as BoardMessage},
-    game_data::GameData,
-};
+pub use self::board::{Board, Evaluation, Message as BoardMessage};
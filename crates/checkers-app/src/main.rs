@@ -25,6 +25,8 @@ fn main() -> Result {
         antialiasing: true,
         exit_on_close_request: false,
         window: window::Settings {
+            // Начальный размер окна, доска любого размера масштабируется так, чтобы поместиться в окно
+            size: (1280, 900),
             position: window::Position::Centered,
             ..window::Settings::default()
         },
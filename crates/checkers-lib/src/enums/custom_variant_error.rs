@@ -0,0 +1,101 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::{error::Error, fmt, io};
+
+/// Ошибка загрузки пользовательских разновидностей игры
+#[derive(Debug)]
+pub enum CustomVariantError {
+    /// Не удалось прочитать файл с правилами
+    Io(io::Error),
+    /// Файл с правилами не является корректным TOML или содержит неизвестные параметры
+    Parse(toml::de::Error),
+    /// У разновидности игры не указано название
+    EmptyName,
+    /// Разновидность игры с таким названием уже существует
+    DuplicateName(String),
+    /// Размер доски вне допустимого диапазона
+    InvalidBoardSize { name: String, board_size: i8 },
+    /// Начальные ряды шашек не помещаются на доске или отсутствуют
+    InvalidInitialRows {
+        name: String,
+        initial_rows: i8,
+        board_size: i8,
+    },
+    /// Правило большинства указано без обязательного взятия
+    MajorityRuleWithoutMandatoryTaking { name: String },
+}
+
+impl fmt::Display for CustomVariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::structs::rulesets::CustomRuleset;
+        match self {
+            Self::Io(error) => write!(f, "Не удалось прочитать файл с правилами: {}", error),
+            Self::Parse(error) => write!(f, "Ошибка в описании правил: {}", error),
+            Self::EmptyName => write!(f, "У разновидности игры не указано название (name)"),
+            Self::DuplicateName(name) => {
+                write!(f, "Разновидность игры \"{}\" уже существует", name)
+            }
+            Self::InvalidBoardSize { name, board_size } => write!(
+                f,
+                "\"{}\": размер доски (board_size) должен быть от {} до {}, указано {}",
+                name,
+                CustomRuleset::MIN_BOARD_SIZE,
+                CustomRuleset::MAX_BOARD_SIZE,
+                board_size
+            ),
+            Self::InvalidInitialRows {
+                name,
+                initial_rows,
+                board_size,
+            } => write!(
+                f,
+                "\"{}\": на доске {}x{} количество рядов шашек (initial_rows) должно быть от 1 до {}, указано {}",
+                name,
+                board_size,
+                board_size,
+                CustomRuleset::max_initial_rows(*board_size),
+                initial_rows
+            ),
+            Self::MajorityRuleWithoutMandatoryTaking { name } => write!(
+                f,
+                "\"{}\": правило большинства (majority_capture) требует обязательного взятия (mandatory_capture)",
+                name
+            ),
+        }
+    }
+}
+
+impl Error for CustomVariantError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CustomVariantError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for CustomVariantError {
+    fn from(error: toml::de::Error) -> Self {
+        Self::Parse(error)
+    }
+}
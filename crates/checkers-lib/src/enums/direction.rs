@@ -13,11 +13,79 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
+use crate::enums::Side;
 
+/// Направление перемещения фигуры по доске
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum Direction {
     LeftUp,
     RightUp,
     LeftDown,
     RightDown,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Все диагональные направления
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::LeftUp,
+        Direction::RightUp,
+        Direction::LeftDown,
+        Direction::RightDown,
+    ];
+
+    /// Все ортогональные направления (по вертикали и горизонтали)
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Все направления: диагональные и ортогональные
+    pub const ALL: [Direction; 8] = [
+        Direction::LeftUp,
+        Direction::RightUp,
+        Direction::LeftDown,
+        Direction::RightDown,
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Возвращает смещение (по строкам, по столбцам) при перемещении на одну клетку в данном направлении
+    pub fn offset(&self) -> (i8, i8) {
+        match self {
+            Direction::LeftUp => (-1, -1),
+            Direction::RightUp => (-1, 1),
+            Direction::LeftDown => (1, -1),
+            Direction::RightDown => (1, 1),
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    /// Возвращает диагональные направления "вперёд" для указанной стороны
+    ///
+    /// Белые начинают игру снизу доски и двигаются вверх, черные - наоборот
+    pub fn forward_diagonals(side: Side) -> [Direction; 2] {
+        match side {
+            Side::White => [Direction::LeftUp, Direction::RightUp],
+            Side::Black => [Direction::LeftDown, Direction::RightDown],
+        }
+    }
+
+    /// Возвращает ортогональное направление "вперёд" для указанной стороны
+    pub fn forward_orthogonal(side: Side) -> Direction {
+        match side {
+            Side::White => Direction::Up,
+            Side::Black => Direction::Down,
+        }
+    }
 }
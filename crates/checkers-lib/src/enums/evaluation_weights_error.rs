@@ -0,0 +1,66 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{error::Error, fmt, io};
+
+/// Ошибка сохранения или загрузки весов оценки позиции
+#[derive(Debug)]
+pub enum EvaluationWeightsError {
+    /// Не удалось прочитать или записать файл весов
+    Io(io::Error),
+    /// Файл не является корректным TOML или в нём указаны не все веса
+    Parse(toml::de::Error),
+    /// Не удалось записать веса в формате TOML
+    Serialize(toml::ser::Error),
+}
+
+impl fmt::Display for EvaluationWeightsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Ошибка чтения или записи весов оценки: {}", error),
+            Self::Parse(error) => write!(f, "Некорректный файл весов оценки: {}", error),
+            Self::Serialize(error) => write!(f, "Не удалось записать веса оценки: {}", error),
+        }
+    }
+}
+
+impl Error for EvaluationWeightsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(error),
+            Self::Serialize(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for EvaluationWeightsError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for EvaluationWeightsError {
+    fn from(error: toml::de::Error) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<toml::ser::Error> for EvaluationWeightsError {
+    fn from(error: toml::ser::Error) -> Self {
+        Self::Serialize(error)
+    }
+}
@@ -0,0 +1,43 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use derive_more::Display;
+
+use crate::enums::PositionViolation;
+
+/// Ошибка разбора позиции, записанной в формате FEN
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum FenError {
+    /// Не указана сторона, которая ходит, или указана не "W" и не "B"
+    #[display(
+        fmt = "Сторона, которая ходит, должна быть указана как W или B, указано \"{}\"",
+        _0
+    )]
+    InvalidSideToMove(String),
+    /// Список фигур не начинается с "W" или "B"
+    #[display(fmt = "Список фигур должен начинаться с W или B: \"{}\"", _0)]
+    InvalidPieceList(String),
+    /// Номер ячейки не является числом или вне доски
+    #[display(fmt = "Некорректный номер ячейки: \"{}\"", _0)]
+    InvalidSquare(String),
+    /// Позиция записана верно, но не может возникнуть в партии
+    #[display(
+        fmt = "Недопустимая позиция: {}",
+        "_0.iter().map(ToString::to_string).collect::<Vec<_>>().join(\"; \")"
+    )]
+    IllegalPosition(Vec<PositionViolation>),
+}
+
+impl std::error::Error for FenError {}
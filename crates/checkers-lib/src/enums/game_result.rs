@@ -0,0 +1,74 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use derive_more::Display;
+
+use crate::enums::Side;
+
+/// Результат партии
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameResult {
+    #[display(fmt = "Победили белые")]
+    WhiteWins,
+    #[display(fmt = "Победили чёрные")]
+    BlackWins,
+    #[display(fmt = "Ничья")]
+    Draw,
+    /// Партия не окончена или результат неизвестен
+    #[display(fmt = "Партия не окончена")]
+    Unfinished,
+}
+
+impl GameResult {
+    /// Результат, при котором побеждает указанная сторона
+    pub fn win(side: Side) -> Self {
+        match side {
+            Side::White => Self::WhiteWins,
+            Side::Black => Self::BlackWins,
+        }
+    }
+
+    /// Победившая сторона, None - при ничьей или неоконченной партии
+    pub fn winner(&self) -> Option<Side> {
+        match self {
+            Self::WhiteWins => Some(Side::White),
+            Self::BlackWins => Some(Side::Black),
+            Self::Draw | Self::Unfinished => None,
+        }
+    }
+
+    /// Запись результата в формате PDN
+    pub fn pdn(&self) -> &'static str {
+        match self {
+            Self::WhiteWins => "1-0",
+            Self::BlackWins => "0-1",
+            Self::Draw => "1/2-1/2",
+            Self::Unfinished => "*",
+        }
+    }
+
+    /// Разбирает результат в формате PDN, в том числе в записи "2-0", "0-2", "1-1",
+    /// принятой в стоклеточных шашках
+    pub fn from_pdn(result: &str) -> Option<Self> {
+        match result {
+            "1-0" | "2-0" => Some(Self::WhiteWins),
+            "0-1" | "0-2" => Some(Self::BlackWins),
+            "1/2-1/2" | "1-1" => Some(Self::Draw),
+            "*" => Some(Self::Unfinished),
+            _ => None,
+        }
+    }
+}
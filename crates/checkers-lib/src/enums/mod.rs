@@ -1,6 +1,26 @@
+mod custom_variant_error;
 mod direction;
+mod evaluation_weights_error;
+mod fen_error;
+mod game_result;
+mod king_range;
+mod move_judgement;
+mod network_error;
+mod opening_book_error;
+mod pdn_error;
 mod piece;
-mod route;
+mod position_violation;
+mod promotion;
 mod side;
+mod tablebase_error;
+mod tablebase_value;
+mod variant;
 
-pub use self::{direction::Direction, piece::Piece, route::Route, side::Side};
+pub use self::{
+    custom_variant_error::CustomVariantError, direction::Direction,
+    evaluation_weights_error::EvaluationWeightsError, fen_error::FenError, game_result::GameResult,
+    king_range::KingRange, move_judgement::MoveJudgement, network_error::NetworkError,
+    opening_book_error::OpeningBookError, pdn_error::PdnError, piece::Piece,
+    position_violation::PositionViolation, promotion::Promotion, side::Side,
+    tablebase_error::TablebaseError, tablebase_value::TablebaseValue, variant::Variant,
+};
@@ -0,0 +1,58 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use derive_more::Display;
+
+/// Оценка хода при анализе партии по тому, насколько он хуже лучшего хода
+#[derive(Debug, Display, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MoveJudgement {
+    #[display(fmt = "Неточность")]
+    Inaccuracy,
+    #[display(fmt = "Ошибка")]
+    Mistake,
+    #[display(fmt = "Зевок")]
+    Blunder,
+}
+
+impl MoveJudgement {
+    /// Наименьшая потеря в оценке (в сотых долях шашки), при которой ход считается неточностью
+    pub const INACCURACY_THRESHOLD: i32 = 30;
+    /// Наименьшая потеря в оценке, при которой ход считается ошибкой
+    pub const MISTAKE_THRESHOLD: i32 = 80;
+    /// Наименьшая потеря в оценке, при которой ход считается зевком
+    pub const BLUNDER_THRESHOLD: i32 = 200;
+
+    /// Оценивает ход по потере в оценке относительно лучшего хода
+    pub fn from_delta(delta: i32) -> Option<Self> {
+        if delta >= Self::BLUNDER_THRESHOLD {
+            Some(Self::Blunder)
+        } else if delta >= Self::MISTAKE_THRESHOLD {
+            Some(Self::Mistake)
+        } else if delta >= Self::INACCURACY_THRESHOLD {
+            Some(Self::Inaccuracy)
+        } else {
+            None
+        }
+    }
+
+    /// Возвращает обозначение оценки хода, принятое в записи партий
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Inaccuracy => "?!",
+            Self::Mistake => "?",
+            Self::Blunder => "??",
+        }
+    }
+}
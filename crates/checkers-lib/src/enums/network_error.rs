@@ -0,0 +1,57 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{error::Error, fmt, io};
+
+/// Ошибка сохранения или загрузки весов нейросетевой оценки
+#[derive(Debug)]
+pub enum NetworkError {
+    /// Не удалось прочитать или записать файл сети
+    Io(io::Error),
+    /// Файл повреждён или не является файлом сети
+    InvalidFormat(String),
+    /// Сеть обучена для другой разновидности игры
+    VariantMismatch { expected: String, found: String },
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Ошибка чтения или записи нейросети: {}", error),
+            Self::InvalidFormat(reason) => write!(f, "Некорректный файл нейросети: {}", reason),
+            Self::VariantMismatch { expected, found } => write!(
+                f,
+                "Нейросеть обучена для \"{}\", а нужна для \"{}\"",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl Error for NetworkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NetworkError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
@@ -0,0 +1,59 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{error::Error, fmt, io};
+
+/// Ошибка сохранения или загрузки дебютной книги
+#[derive(Debug)]
+pub enum OpeningBookError {
+    /// Не удалось прочитать или записать файл книги
+    Io(io::Error),
+    /// Файл повреждён или не является дебютной книгой
+    InvalidFormat(String),
+    /// Книга составлена для другой разновидности игры
+    VariantMismatch { expected: String, found: String },
+}
+
+impl fmt::Display for OpeningBookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Ошибка чтения или записи дебютной книги: {}", error),
+            Self::InvalidFormat(reason) => {
+                write!(f, "Некорректный файл дебютной книги: {}", reason)
+            }
+            Self::VariantMismatch { expected, found } => write!(
+                f,
+                "Дебютная книга составлена для \"{}\", а нужна для \"{}\"",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl Error for OpeningBookError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for OpeningBookError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
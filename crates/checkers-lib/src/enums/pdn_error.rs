@@ -0,0 +1,35 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use derive_more::Display;
+
+use crate::enums::FenError;
+
+/// Ошибка разбора партии, записанной в формате PDN
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum PdnError {
+    /// В теге GameType указана разновидность игры, которая не поддерживается
+    #[display(fmt = "Неизвестная разновидность игры в теге GameType: \"{}\"", _0)]
+    UnknownGameType(String),
+    /// Позиция в теге FEN записана с ошибкой
+    #[display(fmt = "Некорректная начальная позиция в теге FEN: {}", _0)]
+    InvalidFen(FenError),
+    /// Ход недоступен в позиции, которая получилась после предыдущих ходов
+    #[display(fmt = "Недопустимый ход \"{}\" (полуход {})", notation, ply)]
+    IllegalMove { ply: usize, notation: String },
+}
+
+impl std::error::Error for PdnError {}
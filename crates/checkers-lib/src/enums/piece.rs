@@ -18,7 +18,7 @@ use derive_more::Display;
 use serde::{Deserialize, Serialize};
 
 /// Тип фигуры на шашечной доске
-#[derive(Debug, Display, Default, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Display, Default, Hash, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
 pub enum Piece {
     #[default]
     #[display(fmt = "Шашка")]
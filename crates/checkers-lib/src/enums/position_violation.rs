@@ -0,0 +1,50 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use derive_more::Display;
+
+use crate::{enums::Side, structs::Position};
+
+/// Нарушение, из-за которого позиция не может возникнуть в партии
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum PositionViolation {
+    /// Фигура находится за пределами доски
+    #[display(fmt = "{}: фигура за пределами доски {}", side, position)]
+    OutsideBoard { side: Side, position: Position },
+    /// Фигура находится на ячейке, по которой не ходят (например, на светлом поле)
+    #[display(fmt = "{}: фигура на неигровой ячейке {}", side, position)]
+    UnplayableCell { side: Side, position: Position },
+    /// На одной ячейке находятся фигуры обеих сторон
+    #[display(fmt = "На ячейке {} фигуры обеих сторон", _0)]
+    OccupiedByBothSides(Position),
+    /// У стороны больше фигур, чем в начальной позиции
+    #[display(
+        fmt = "{}: фигур {}, больше, чем в начальной позиции ({})",
+        side,
+        count,
+        max
+    )]
+    TooManyPieces {
+        side: Side,
+        count: usize,
+        max: usize,
+    },
+    /// Шашка стоит на горизонтали, на которой она должна была стать дамкой
+    #[display(fmt = "{}: шашка на последней горизонтали {}", side, position)]
+    ManOnPromotionRow { side: Side, position: Position },
+    /// У стороны нет ни одной фигуры
+    #[display(fmt = "{}: нет ни одной фигуры", _0)]
+    NoPieces(Side),
+}
@@ -0,0 +1,25 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use serde::{Deserialize, Serialize};
+
+/// Поведение шашки, достигшей последней горизонтали во время взятия
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Promotion {
+    /// Шашка сразу становится дамкой и продолжает взятие уже как дамка
+    ContinuesMove,
+    /// Шашка становится дамкой только если закончила ход на последней горизонтали
+    AtEndOfMove,
+}
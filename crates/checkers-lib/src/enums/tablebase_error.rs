@@ -0,0 +1,76 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{error::Error, fmt, io};
+
+/// Ошибка построения, сохранения или загрузки таблиц эндшпиля
+#[derive(Debug)]
+pub enum TablebaseError {
+    /// Не удалось прочитать или записать файл с таблицами
+    Io(io::Error),
+    /// Файл повреждён или не является файлом таблиц
+    InvalidFormat(String),
+    /// Таблицы построены для другой разновидности игры
+    VariantMismatch { expected: String, found: String },
+    /// Правила разновидности зависят от истории партии (например, ограничение ходов дамками),
+    /// поэтому результат позиции нельзя определить только по расстановке фигур
+    UnsupportedVariant(String),
+    /// Запрошено больше фигур, чем поддерживают таблицы
+    TooManyPieces(u32),
+}
+
+impl fmt::Display for TablebaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::structs::Tablebase;
+        match self {
+            Self::Io(error) => write!(f, "Ошибка чтения или записи таблиц эндшпиля: {}", error),
+            Self::InvalidFormat(reason) => {
+                write!(f, "Некорректный файл таблиц эндшпиля: {}", reason)
+            }
+            Self::VariantMismatch { expected, found } => write!(
+                f,
+                "Таблицы эндшпиля построены для \"{}\", а нужны для \"{}\"",
+                found, expected
+            ),
+            Self::UnsupportedVariant(name) => write!(
+                f,
+                "Для \"{}\" таблицы эндшпиля не строятся: правила зависят от истории партии",
+                name
+            ),
+            Self::TooManyPieces(pieces) => write!(
+                f,
+                "Таблицы эндшпиля строятся не более чем для {} фигур, указано {}",
+                Tablebase::MAX_PIECES,
+                pieces
+            ),
+        }
+    }
+}
+
+impl Error for TablebaseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TablebaseError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
@@ -0,0 +1,71 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use derive_more::Display;
+
+/// Результат позиции из таблицы эндшпиля с точки зрения стороны, которая сейчас ходит
+///
+/// Расстояние до конца партии считается в полуходах при наилучшей игре обеих сторон:
+/// выигрывающая сторона стремится закончить партию быстрее, проигрывающая - затянуть
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TablebaseValue {
+    /// Выигрыш за указанное количество полуходов
+    #[display(fmt = "Выигрыш за {} полуход(ов)", _0)]
+    Win(u32),
+    /// Проигрыш через указанное количество полуходов
+    #[display(fmt = "Проигрыш через {} полуход(ов)", _0)]
+    Loss(u32),
+    /// Ничья
+    #[display(fmt = "Ничья")]
+    Draw,
+}
+
+impl TablebaseValue {
+    /// Результат позиции для стороны, сделавшей ход, который привёл к этой позиции
+    pub fn for_previous_move(self) -> Self {
+        match self {
+            Self::Win(plies) => Self::Loss(plies + 1),
+            Self::Loss(plies) => Self::Win(plies + 1),
+            Self::Draw => Self::Draw,
+        }
+    }
+
+    /// Ключ для сравнения результатов: чем больше, тем лучше для стороны, которая ходит
+    pub fn preference(self) -> i64 {
+        match self {
+            Self::Win(plies) => i64::MAX - plies as i64,
+            Self::Draw => 0,
+            Self::Loss(plies) => i64::MIN + plies as i64,
+        }
+    }
+
+    /// Код результата в файле таблиц: 0 - ничья, 2d + 1 - выигрыш, 2d + 2 - проигрыш
+    pub(crate) fn code(self) -> u16 {
+        match self {
+            Self::Draw => 0,
+            Self::Win(plies) => (2 * plies + 1) as u16,
+            Self::Loss(plies) => (2 * plies + 2) as u16,
+        }
+    }
+
+    pub(crate) fn from_code(code: u16) -> Self {
+        match code {
+            0 => Self::Draw,
+            _ if code % 2 == 1 => Self::Win((code as u32 - 1) / 2),
+            _ => Self::Loss((code as u32 - 2) / 2),
+        }
+    }
+}
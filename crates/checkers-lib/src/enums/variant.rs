@@ -0,0 +1,149 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::{path::Path, sync::Arc};
+
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::CustomVariantError,
+    structs::rulesets::{
+        BrazilianRuleset, CanadianRuleset, CustomRuleset, EnglishRuleset, FrisianRuleset,
+        GiveawayRuleset, InternationalRuleset, ItalianRuleset, RussianRuleset, SpanishRuleset,
+        TurkishRuleset,
+    },
+    traits::Ruleset,
+};
+
+/// Разновидность игры в шашки
+#[derive(Debug, Display, Default, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Variant {
+    #[default]
+    #[display(fmt = "Русские шашки")]
+    Russian,
+    #[display(fmt = "Международные шашки")]
+    International,
+    #[display(fmt = "Английские шашки")]
+    English,
+    #[display(fmt = "Итальянские шашки")]
+    Italian,
+    #[display(fmt = "Испанские шашки")]
+    Spanish,
+    #[display(fmt = "Португальские шашки")]
+    Portuguese,
+    #[display(fmt = "Бразильские шашки")]
+    Brazilian,
+    #[display(fmt = "Турецкие шашки")]
+    Turkish,
+    #[display(fmt = "Фризские шашки")]
+    Frisian,
+    #[display(fmt = "Канадские шашки")]
+    Canadian,
+    #[display(fmt = "Поддавки")]
+    Giveaway,
+    /// Правила, описанные пользователем
+    #[display(fmt = "{}", "_0.name")]
+    Custom(Arc<CustomRuleset>),
+}
+
+impl Variant {
+    /// Все встроенные разновидности игры
+    pub const ALL: [Variant; 11] = [
+        Variant::Russian,
+        Variant::International,
+        Variant::English,
+        Variant::Italian,
+        Variant::Spanish,
+        Variant::Portuguese,
+        Variant::Brazilian,
+        Variant::Turkish,
+        Variant::Frisian,
+        Variant::Canadian,
+        Variant::Giveaway,
+    ];
+
+    /// Возвращает правила данной разновидности игры
+    pub fn ruleset(&self) -> &dyn Ruleset {
+        match self {
+            Variant::Russian => &RussianRuleset,
+            Variant::International => &InternationalRuleset,
+            Variant::English => &EnglishRuleset,
+            Variant::Italian => &ItalianRuleset,
+            Variant::Spanish | Variant::Portuguese => &SpanishRuleset,
+            Variant::Brazilian => &BrazilianRuleset,
+            Variant::Turkish => &TurkishRuleset,
+            Variant::Frisian => &FrisianRuleset,
+            Variant::Canadian => &CanadianRuleset,
+            // Поддавки по правилам русских шашек
+            Variant::Giveaway => &GiveawayRuleset(RussianRuleset),
+            Variant::Custom(ruleset) => ruleset.as_ref(),
+        }
+    }
+
+    /// Возвращает номер разновидности игры в теге GameType формата PDN
+    pub fn pdn_game_type(&self) -> Option<u32> {
+        match self {
+            Variant::International => Some(20),
+            Variant::English => Some(21),
+            Variant::Italian => Some(22),
+            Variant::Spanish => Some(24),
+            Variant::Russian => Some(25),
+            Variant::Brazilian => Some(26),
+            Variant::Canadian => Some(27),
+            Variant::Portuguese => Some(28),
+            Variant::Turkish => Some(30),
+            Variant::Frisian => Some(40),
+            Variant::Giveaway | Variant::Custom(_) => None,
+        }
+    }
+
+    /// Возвращает встроенную разновидность игры по номеру в теге GameType формата PDN
+    pub fn from_pdn_game_type(game_type: u32) -> Option<Variant> {
+        Self::ALL
+            .into_iter()
+            .find(|variant| variant.pdn_game_type() == Some(game_type))
+    }
+
+    /// Возвращает имя файла данных для разновидности игры (дебютной книги, таблиц эндшпиля
+    /// и т.п.) с указанным расширением
+    ///
+    /// Имя составляется из названия правил: все символы, кроме букв, цифр, пробела, "-" и "_",
+    /// заменяются на "_", чтобы название пользовательских правил не могло указать на файл
+    /// в другой директории
+    pub fn file_name(&self, extension: &str) -> String {
+        let name: String = self
+            .to_string()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let name = name.trim();
+        format!("{}.{}", if name.is_empty() { "_" } else { name }, extension)
+    }
+
+    /// Загружает пользовательские разновидности игры из TOML файла
+    pub fn load_custom(path: impl AsRef<Path>) -> Result<Vec<Variant>, CustomVariantError> {
+        Ok(CustomRuleset::load_variants(path)?
+            .into_iter()
+            .map(|ruleset| Variant::Custom(Arc::new(ruleset)))
+            .collect())
+    }
+}
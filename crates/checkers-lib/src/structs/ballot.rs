@@ -0,0 +1,120 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::fmt;
+
+use crate::{
+    enums::{Side, Variant},
+    structs::{GameData, Move},
+};
+
+/// Дебют, с которого начинается партия: несколько первых ходов, выбранных жребием
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ballot {
+    /// Ходы дебюта в числовой нотации
+    pub notation: Vec<String>,
+    /// Ходы дебюта
+    pub moves: Vec<Move>,
+}
+
+impl fmt::Display for Ballot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.notation.join(" "))
+    }
+}
+
+impl Ballot {
+    /// Разбирает дебют, записанный ходами в числовой нотации через пробел, например "11-15 23-19 8-11"
+    ///
+    /// Возвращает None, если какой-либо из ходов недоступен в начальной позиции данной разновидности игры
+    pub fn parse(variant: Variant, notation: &str) -> Option<Self> {
+        let mut game_data = GameData::new(variant);
+        let mut ballot = Self {
+            notation: Vec::new(),
+            moves: Vec::new(),
+        };
+        for move_notation in notation.split_whitespace() {
+            let piece_move = game_data.parse_move(move_notation)?;
+            let side = game_data.current_move;
+            game_data.make_move(side, &piece_move);
+            ballot.notation.push(move_notation.to_string());
+            ballot.moves.push(piece_move);
+        }
+        Some(ballot)
+    }
+
+    /// Делает ходы дебюта в партии
+    ///
+    /// Партия должна находиться в начальной позиции той разновидности игры, для которой составлен дебют
+    pub fn play(&self, game_data: &mut GameData) {
+        for piece_move in &self.moves {
+            let side = game_data.current_move;
+            game_data.make_move(side, piece_move);
+        }
+    }
+
+    /// Создаёт партию, начатую с данного дебюта
+    pub fn game_data(&self, variant: Variant) -> GameData {
+        let mut game_data = GameData::new(variant);
+        self.play(&mut game_data);
+        game_data
+    }
+}
+
+/// Колода дебютов, из которой жребием выбирается начало партии
+#[derive(Debug, Clone)]
+pub struct BallotDeck {
+    /// Разновидность игры, для которой составлены дебюты
+    pub variant: Variant,
+    pub ballots: Vec<Ballot>,
+}
+
+impl BallotDeck {
+    /// Колода трёхходовых дебютов английских шашек
+    ///
+    /// Составлена движком, а не взята из официальной колоды ACF: содержит различные позиции после
+    /// трёх ходов, оценка которых перебором на глубину 10 по модулю меньше половины шашки
+    pub fn english() -> Self {
+        let variant = Variant::English;
+        let ballots = include_str!("../../data/english_ballots.txt")
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                Ballot::parse(variant.clone(), line)
+                    .unwrap_or_else(|| panic!("некорректный дебют в колоде: {}", line))
+            })
+            .collect();
+        Self { variant, ballots }
+    }
+
+    /// Возвращает колоду дебютов для данной разновидности игры, если она есть
+    pub fn for_variant(variant: &Variant) -> Option<Self> {
+        match variant {
+            Variant::English => Some(Self::english()),
+            _ => None,
+        }
+    }
+
+    /// Перебирает партии матча по колоде: каждый дебют играется дважды, со сменой цвета
+    ///
+    /// Возвращает дебют и цвет, которым в этой партии играет первый участник матча
+    pub fn games(&self) -> impl Iterator<Item = (&Ballot, Side)> + Clone {
+        let first_side = self.variant.ruleset().first_move();
+        self.ballots
+            .iter()
+            .flat_map(move |ballot| [(ballot, first_side), (ballot, first_side.opposite())])
+    }
+}
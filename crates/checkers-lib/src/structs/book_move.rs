@@ -0,0 +1,66 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::enums::{GameResult, Side};
+
+/// Ход из дебютной книги со статистикой партий, в которых он был сделан
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BookMove {
+    /// Ход в числовой нотации
+    pub notation: String,
+    /// Вес хода: чем больше, тем охотнее движок его выбирает
+    pub weight: u32,
+    /// Количество партий с этим ходом
+    pub games: u32,
+    pub white_wins: u32,
+    pub draws: u32,
+    pub black_wins: u32,
+}
+
+impl BookMove {
+    /// Учитывает партию, в которой ход сделала указанная сторона
+    ///
+    /// Вес хода растёт на 2 за каждую победу сделавшей его стороны и на 1 за каждую ничью
+    pub fn add_game(&mut self, side: Side, result: GameResult) {
+        self.games += 1;
+        match result {
+            GameResult::WhiteWins => self.white_wins += 1,
+            GameResult::BlackWins => self.black_wins += 1,
+            GameResult::Draw => self.draws += 1,
+            GameResult::Unfinished => {}
+        }
+        self.weight += match result.winner() {
+            Some(winner) if winner == side => 2,
+            None if result == GameResult::Draw => 1,
+            _ => 0,
+        };
+    }
+
+    /// Доля партий с известным результатом, окончившихся указанным образом, в процентах
+    pub fn percentage(&self, result: GameResult) -> f32 {
+        let finished = self.white_wins + self.draws + self.black_wins;
+        if finished == 0 {
+            return 0.0;
+        }
+        let count = match result {
+            GameResult::WhiteWins => self.white_wins,
+            GameResult::BlackWins => self.black_wins,
+            GameResult::Draw => self.draws,
+            GameResult::Unfinished => 0,
+        };
+        100.0 * count as f32 / finished as f32
+    }
+}
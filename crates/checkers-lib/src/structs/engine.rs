@@ -0,0 +1,434 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    enums::{MoveJudgement, TablebaseValue},
+    structs::{
+        evaluators::{Accumulator, HandcraftedEvaluator},
+        GameData, GameRecord, Move, MoveAnalysis, OpeningBook, Tablebase,
+    },
+    traits::Evaluator,
+};
+
+/// Результат перебора
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    /// Лучший найденный ход, None - если ходов нет
+    pub best_move: Option<Move>,
+    /// Оценка позиции с точки зрения стороны, которая сейчас ходит
+    pub score: i32,
+    /// Главный вариант: ожидаемая последовательность ходов обеих сторон, начиная с лучшего хода
+    pub principal_variation: Vec<Move>,
+    /// Глубина, на которую был выполнен перебор
+    pub depth: u32,
+    /// false - если перебор был прерван и результат недостоверен
+    pub completed: bool,
+}
+
+/// Движок, выбирающий ход перебором с альфа-бета отсечением
+#[derive(Clone)]
+pub struct Engine {
+    /// Глубина перебора в полуходах
+    depth: u32,
+    /// Статическая оценка позиции
+    evaluator: Arc<dyn Evaluator>,
+    /// Флаг, по которому перебор прерывается досрочно
+    stop: Option<Arc<AtomicBool>>,
+    /// Момент, после которого перебор прерывается
+    deadline: Option<Instant>,
+    /// Таблицы эндшпиля, по которым точно оцениваются позиции с небольшим количеством фигур
+    tablebase: Option<Arc<Tablebase>>,
+    /// Дебютная книга, ходы из которой делаются без перебора
+    book: Option<Arc<OpeningBook>>,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DEPTH)
+    }
+}
+
+impl Engine {
+    /// Глубина перебора по умолчанию
+    pub const DEFAULT_DEPTH: u32 = 6;
+    /// Оценка выигранной позиции. Выигрыш в меньшее количество ходов оценивается выше
+    pub const WIN_SCORE: i32 = 1_000_000;
+
+    pub fn new(depth: u32) -> Self {
+        Self {
+            depth,
+            evaluator: Arc::new(HandcraftedEvaluator::default()),
+            stop: None,
+            deadline: None,
+            tablebase: None,
+            book: None,
+        }
+    }
+
+    /// Заменяет статическую оценку позиции
+    pub fn with_evaluator(mut self, evaluator: Arc<dyn Evaluator>) -> Self {
+        self.evaluator = evaluator;
+        self
+    }
+
+    /// Позволяет прервать перебор из другого потока, установив флаг в true
+    pub fn with_stop_flag(mut self, stop: Arc<AtomicBool>) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// Подключает таблицы эндшпиля
+    pub fn with_tablebase(mut self, tablebase: Arc<Tablebase>) -> Self {
+        self.tablebase = Some(tablebase);
+        self
+    }
+
+    pub fn tablebase(&self) -> Option<&Tablebase> {
+        self.tablebase.as_deref()
+    }
+
+    /// Подключает дебютную книгу
+    pub fn with_book(mut self, book: Arc<OpeningBook>) -> Self {
+        self.book = Some(book);
+        self
+    }
+
+    pub fn book(&self) -> Option<&OpeningBook> {
+        self.book.as_deref()
+    }
+
+    /// Копия движка с другой глубиной перебора
+    pub fn with_depth(&self, depth: u32) -> Self {
+        Self {
+            depth,
+            ..self.clone()
+        }
+    }
+
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Проверяет, означает ли оценка выигрыш или проигрыш
+    pub fn is_win_score(score: i32) -> bool {
+        score.abs() > Self::WIN_SCORE / 2
+    }
+
+    /// Возвращает лучший ход для стороны, которая сейчас ходит
+    ///
+    /// Если позиция есть в дебютной книге, ход берётся из книги без перебора
+    pub fn best_move(&self, game_data: &GameData) -> Option<Move> {
+        self.book
+            .as_ref()
+            .and_then(|book| book.best_move(game_data))
+            .or_else(|| self.search(game_data).best_move)
+    }
+
+    /// Возвращает лучший ход, найденный за отведённое время (см. [Engine::search_for])
+    ///
+    /// Если позиция есть в дебютной книге, ход берётся из книги без перебора
+    pub fn best_move_within(&self, game_data: &GameData, time_limit: Duration) -> Option<Move> {
+        self.book
+            .as_ref()
+            .and_then(|book| book.best_move(game_data))
+            .or_else(|| self.search_for(game_data, time_limit).best_move)
+    }
+
+    /// Перебирает позицию с итеративным углублением, пока не истечёт отведённое время
+    /// или не будет достигнута глубина движка
+    ///
+    /// Возвращает результат последней полностью перебранной глубины
+    pub fn search_for(&self, game_data: &GameData, time_limit: Duration) -> SearchResult {
+        let engine = Self {
+            deadline: Some(Instant::now() + time_limit),
+            ..self.clone()
+        };
+        engine
+            .iterative_deepening(game_data)
+            .last()
+            // Даже перебор на один полуход не уложился во время, ход всё равно нужен
+            .unwrap_or_else(|| self.with_depth(1).search(game_data))
+    }
+
+    /// Перебирает ходы на заданную глубину
+    pub fn search(&self, game_data: &GameData) -> SearchResult {
+        let mut principal_variation = Vec::new();
+        let accumulator = self.evaluator.new_accumulator(game_data);
+        let score = self.negamax(
+            game_data,
+            accumulator.as_ref(),
+            self.depth,
+            0,
+            -Self::WIN_SCORE - 1,
+            Self::WIN_SCORE + 1,
+            &mut principal_variation,
+        );
+        SearchResult {
+            best_move: principal_variation.first().cloned(),
+            score,
+            principal_variation,
+            depth: self.depth,
+            completed: !self.is_stopped(),
+        }
+    }
+
+    /// Итеративное углубление: последовательно перебирает позицию на глубину от 1 до заданной
+    ///
+    /// Итератор заканчивается досрочно, если перебор был прерван
+    pub fn iterative_deepening<'a>(
+        &'a self,
+        game_data: &'a GameData,
+    ) -> impl Iterator<Item = SearchResult> + 'a {
+        (1..=self.depth)
+            .map(move |depth| self.with_depth(depth).search(game_data))
+            .take_while(|result| result.completed)
+    }
+
+    /// Анализирует партию: сравнивает каждый сделанный ход с лучшим ходом по мнению движка
+    pub fn analyse(&self, record: &GameRecord) -> Vec<MoveAnalysis> {
+        // Позиция после хода перебирается на полуход меньше, чтобы глубина совпадала с перебором до хода
+        let reply_engine = self.with_depth(self.depth.saturating_sub(1));
+        record
+            .positions()
+            .iter()
+            .zip(&record.moves)
+            .map(|(game_data, played_move)| {
+                let side = game_data.current_move;
+                let best = self.search(game_data);
+                let played_score = if best.best_move.as_ref() == Some(played_move) {
+                    best.score
+                } else {
+                    let mut child = game_data.clone();
+                    child.make_move(side, played_move);
+                    let score = reply_engine.search(&child).score;
+                    if child.current_move == side {
+                        score
+                    } else {
+                        -score
+                    }
+                };
+                let mut analysis = MoveAnalysis {
+                    side,
+                    played_move: played_move.clone(),
+                    played_notation: game_data.move_notation(played_move),
+                    best_notation: best
+                        .best_move
+                        .as_ref()
+                        .map(|best_move| game_data.move_notation(best_move)),
+                    best_move: best.best_move,
+                    best_score: best.score,
+                    played_score,
+                    judgement: None,
+                };
+                analysis.judgement = MoveJudgement::from_delta(analysis.delta());
+                analysis
+            })
+            .collect()
+    }
+
+    /// Статическая оценка позиции с точки зрения стороны, которая сейчас ходит
+    ///
+    /// В игре "в поддавки" оценка инвертируется: выгодно избавляться от своих фигур
+    pub fn evaluate(&self, game_data: &GameData) -> i32 {
+        self.evaluate_with(game_data, None)
+    }
+
+    /// Статическая оценка позиции по аккумулятору, если оценка его поддерживает
+    fn evaluate_with(&self, game_data: &GameData, accumulator: Option<&Accumulator>) -> i32 {
+        let score = match accumulator {
+            Some(accumulator) => self.evaluator.evaluate_accumulated(accumulator, game_data),
+            None => self.evaluator.evaluate(game_data),
+        };
+        if game_data.ruleset().is_giveaway() {
+            -score
+        } else {
+            score
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stop
+            .as_ref()
+            .is_some_and(|stop| stop.load(Ordering::Relaxed))
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Оценка позиции по таблицам эндшпиля, если позиция в них есть
+    fn probe_tablebase(&self, game_data: &GameData, ply: u32) -> Option<i32> {
+        let value = self.tablebase.as_ref()?.probe(game_data)?;
+        Some(match value {
+            TablebaseValue::Win(plies) => Self::WIN_SCORE - (ply + plies) as i32,
+            TablebaseValue::Loss(plies) => -(Self::WIN_SCORE - (ply + plies) as i32),
+            TablebaseValue::Draw => 0,
+        })
+    }
+
+    /// Оценка позиции, в которой у стороны, которая сейчас ходит, нет ходов
+    fn terminal_score(game_data: &GameData, ply: u32) -> i32 {
+        let score = Self::WIN_SCORE - ply as i32;
+        if game_data.winner() == Some(game_data.current_move) {
+            score
+        } else {
+            -score
+        }
+    }
+
+    /// Аккумулятор позиции после хода, полученный обновлением аккумулятора позиции до хода
+    fn child_accumulator(
+        &self,
+        accumulator: Option<&Accumulator>,
+        before: &GameData,
+        piece_move: &Move,
+        after: &GameData,
+    ) -> Option<Accumulator> {
+        let mut accumulator = accumulator?.clone();
+        self.evaluator
+            .update_accumulator(&mut accumulator, before, piece_move, after);
+        Some(accumulator)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(
+        &self,
+        game_data: &GameData,
+        accumulator: Option<&Accumulator>,
+        depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+        principal_variation: &mut Vec<Move>,
+    ) -> i32 {
+        if self.is_stopped() {
+            return 0;
+        }
+        // В корне перебор нужен, чтобы выбрать ход, дальше результат берётся из таблиц
+        if ply > 0 {
+            if let Some(score) = self.probe_tablebase(game_data, ply) {
+                return score;
+            }
+        }
+        if depth == 0 {
+            return self.quiescence(game_data, accumulator, ply, alpha, beta);
+        }
+        let moves = game_data.get_all_available_moves();
+        if moves.is_empty() {
+            return Self::terminal_score(game_data, ply);
+        }
+
+        let mut best_score = -Self::WIN_SCORE - 1;
+        for piece_move in moves {
+            let mut child = game_data.clone();
+            child.make_move(game_data.current_move, &piece_move);
+            let child_accumulator =
+                self.child_accumulator(accumulator, game_data, &piece_move, &child);
+            let mut child_variation = Vec::new();
+            // При форе сторона может сделать несколько ходов подряд
+            let score = if child.current_move == game_data.current_move {
+                self.negamax(
+                    &child,
+                    child_accumulator.as_ref(),
+                    depth - 1,
+                    ply + 1,
+                    alpha,
+                    beta,
+                    &mut child_variation,
+                )
+            } else {
+                -self.negamax(
+                    &child,
+                    child_accumulator.as_ref(),
+                    depth - 1,
+                    ply + 1,
+                    -beta,
+                    -alpha,
+                    &mut child_variation,
+                )
+            };
+            if score > best_score {
+                best_score = score;
+                if score > alpha {
+                    alpha = score;
+                    principal_variation.clear();
+                    principal_variation.push(piece_move);
+                    principal_variation.extend(child_variation);
+                }
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        best_score
+    }
+
+    /// Продолжает перебор взятий, чтобы не оценивать позицию в середине размена
+    fn quiescence(
+        &self,
+        game_data: &GameData,
+        accumulator: Option<&Accumulator>,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        let moves = game_data.get_all_available_moves();
+        if moves.is_empty() {
+            return Self::terminal_score(game_data, ply);
+        }
+        let takings: Vec<Move> = moves
+            .iter()
+            .filter(|piece_move| piece_move.is_taking())
+            .cloned()
+            .collect();
+        if takings.is_empty() {
+            return self.evaluate_with(game_data, accumulator);
+        }
+
+        // Если можно не брать, сторона может остаться при текущей оценке
+        if takings.len() < moves.len() {
+            let stand_pat = self.evaluate_with(game_data, accumulator);
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        let mut best_score = -Self::WIN_SCORE - 1;
+        for taking in takings {
+            let mut child = game_data.clone();
+            child.make_move(game_data.current_move, &taking);
+            let child_accumulator = self.child_accumulator(accumulator, game_data, &taking, &child);
+            let score = if child.current_move == game_data.current_move {
+                self.quiescence(&child, child_accumulator.as_ref(), ply + 1, alpha, beta)
+            } else {
+                -self.quiescence(&child, child_accumulator.as_ref(), ply + 1, -beta, -alpha)
+            };
+            best_score = best_score.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best_score.max(alpha)
+    }
+}
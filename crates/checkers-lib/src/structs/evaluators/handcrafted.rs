@@ -0,0 +1,163 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::{EvaluationWeightsError, Piece, Side, Variant},
+    structs::{GameData, Position},
+    traits::Evaluator,
+};
+
+/// Веса слагаемых оценки позиции
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvaluationWeights {
+    /// Ценность шашки
+    pub man: i32,
+    /// Ценность дамки
+    pub king: i32,
+    /// Шашка, охраняющая свою первую горизонталь
+    pub back_rank: i32,
+    /// Фигура в центре доски
+    pub centre: i32,
+    /// Продвижение шашки на одну горизонталь вперёд
+    pub tempo: i32,
+}
+
+impl Default for EvaluationWeights {
+    fn default() -> Self {
+        Self {
+            man: 100,
+            king: 250,
+            back_rank: 10,
+            centre: 5,
+            tempo: 2,
+        }
+    }
+}
+
+impl EvaluationWeights {
+    /// Количество слагаемых оценки
+    pub const LEN: usize = 5;
+
+    /// Возвращает веса в виде массива, в том же порядке, что и признаки позиции
+    pub fn to_array(&self) -> [i32; Self::LEN] {
+        [self.man, self.king, self.back_rank, self.centre, self.tempo]
+    }
+
+    /// Создаёт веса из массива, в том же порядке, что и признаки позиции
+    pub fn from_array(weights: [i32; Self::LEN]) -> Self {
+        let [man, king, back_rank, centre, tempo] = weights;
+        Self {
+            man,
+            king,
+            back_rank,
+            centre,
+            tempo,
+        }
+    }
+
+    /// Загружает веса из файла TOML, например записанного утилитой настройки весов
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EvaluationWeightsError> {
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Сохраняет веса в файл TOML
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EvaluationWeightsError> {
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Имя файла весов для данной разновидности игры
+    pub fn default_file_name(variant: &Variant) -> String {
+        variant.file_name("toml")
+    }
+}
+
+/// Оценка позиции, составленная вручную как взвешенная сумма признаков позиции
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HandcraftedEvaluator {
+    pub weights: EvaluationWeights,
+}
+
+impl HandcraftedEvaluator {
+    pub fn new(weights: EvaluationWeights) -> Self {
+        Self { weights }
+    }
+
+    /// Возвращает признаки позиции: разность значений для белых и черных
+    ///
+    /// Порядок признаков совпадает с порядком весов в [EvaluationWeights::to_array]
+    pub fn features(game_data: &GameData) -> [i32; EvaluationWeights::LEN] {
+        let mut features = [0; EvaluationWeights::LEN];
+        for side in [Side::White, Side::Black] {
+            let sign = match side {
+                Side::White => 1,
+                Side::Black => -1,
+            };
+            for (position, piece) in game_data.pieces(side) {
+                let side_features = Self::piece_features(game_data, side, *position, *piece);
+                for (feature, value) in features.iter_mut().zip(side_features) {
+                    *feature += sign * value;
+                }
+            }
+        }
+        features
+    }
+
+    /// Возвращает вклад одной фигуры в признаки позиции
+    fn piece_features(
+        game_data: &GameData,
+        side: Side,
+        position: Position,
+        piece: Piece,
+    ) -> [i32; EvaluationWeights::LEN] {
+        let (rows, columns) = game_data.board_cells();
+        // Сколько горизонталей фигура прошла от своего края доски
+        let advancement = match side {
+            Side::White => rows - 1 - position.row,
+            Side::Black => position.row,
+        } as i32;
+        let is_centre = (rows / 4..rows - rows / 4).contains(&position.row)
+            && (columns / 4..columns - columns / 4).contains(&position.column);
+        match piece {
+            Piece::Man => [
+                1,
+                0,
+                (advancement == 0) as i32,
+                is_centre as i32,
+                advancement,
+            ],
+            Piece::King => [0, 1, 0, is_centre as i32, 0],
+        }
+    }
+}
+
+impl Evaluator for HandcraftedEvaluator {
+    fn evaluate(&self, game_data: &GameData) -> i32 {
+        let score: i32 = Self::features(game_data)
+            .iter()
+            .zip(self.weights.to_array())
+            .map(|(feature, weight)| feature * weight)
+            .sum();
+        match game_data.current_move {
+            Side::White => score,
+            Side::Black => -score,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod handcrafted;
+mod network;
+
+pub use self::{
+    handcrafted::{EvaluationWeights, HandcraftedEvaluator},
+    network::{Accumulator, Network, NetworkEvaluator},
+};
@@ -0,0 +1,360 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    enums::{NetworkError, Piece, Side, Variant},
+    structs::{GameData, Move, Position},
+    traits::Evaluator,
+};
+
+/// Небольшая полносвязная нейросеть для оценки позиции
+///
+/// Входы - по одному на каждое сочетание игровой ячейки и вида фигуры (белая шашка, белая дамка,
+/// чёрная шашка, чёрная дамка), и ещё один - "ходят чёрные". Скрытый слой с ограниченной
+/// функцией активации ReLU (от 0 до 1), выход - оценка позиции с точки зрения белых
+/// в единицах [Network::OUTPUT_SCALE] сотых долей шашки
+///
+/// Сумма весов активных входов скрытого слоя (аккумулятор) при ходе обновляется только
+/// для изменившихся ячеек, а не вычисляется заново
+#[derive(Debug, Clone, PartialEq)]
+pub struct Network {
+    /// Количество игровых ячеек доски
+    pub squares: usize,
+    /// Размер скрытого слоя
+    pub hidden: usize,
+    /// Веса входов: для каждого входа подряд веса всех нейронов скрытого слоя
+    pub input_weights: Vec<f32>,
+    pub hidden_biases: Vec<f32>,
+    pub output_weights: Vec<f32>,
+    pub output_bias: f32,
+}
+
+/// Состояние скрытого слоя нейросети для позиции: смещения плюс сумма весов активных входов
+#[derive(Debug, Clone, PartialEq)]
+pub struct Accumulator {
+    pub values: Vec<f32>,
+}
+
+impl Network {
+    /// Множитель выхода сети: оценка в сотых долях шашки равна выходу, умноженному на него.
+    /// При этом ожидаемый результат партии равен 1 / (1 + 10^(-выход))
+    pub const OUTPUT_SCALE: f32 = 400.0;
+    /// Количество видов фигур, для каждого из которых у ячейки есть отдельный вход
+    const PIECE_KINDS: usize = 4;
+    /// Первые байты файла с весами сети
+    const MAGIC: &'static [u8; 4] = b"CKNN";
+    /// Версия формата файла
+    const VERSION: u8 = 1;
+
+    /// Создаёт сеть с нулевыми весами
+    pub fn new(squares: usize, hidden: usize) -> Self {
+        let inputs = squares * Self::PIECE_KINDS + 1;
+        Self {
+            squares,
+            hidden,
+            input_weights: vec![0.0; inputs * hidden],
+            hidden_biases: vec![0.0; hidden],
+            output_weights: vec![0.0; hidden],
+            output_bias: 0.0,
+        }
+    }
+
+    /// Количество входов сети
+    pub fn inputs(&self) -> usize {
+        self.squares * Self::PIECE_KINDS + 1
+    }
+
+    /// Номер входа для фигуры на ячейке с данным номером (начиная с единицы)
+    ///
+    /// Порядок видов фигур совпадает с кодами ячеек набора позиций: белая шашка, белая дамка,
+    /// чёрная шашка, чёрная дамка
+    pub fn piece_input(square: usize, side: Side, piece: Piece) -> usize {
+        let kind = match (side, piece) {
+            (Side::White, Piece::Man) => 0,
+            (Side::White, Piece::King) => 1,
+            (Side::Black, Piece::Man) => 2,
+            (Side::Black, Piece::King) => 3,
+        };
+        (square - 1) * Self::PIECE_KINDS + kind
+    }
+
+    /// Номер входа "ходят чёрные"
+    pub fn side_input(&self) -> usize {
+        self.squares * Self::PIECE_KINDS
+    }
+
+    /// Возвращает номера активных входов для позиции в порядке возрастания
+    ///
+    /// Порядок не зависит от порядка обхода фигур, поэтому одна и та же позиция всегда даёт
+    /// одинаковые входы и одинаковую сумму в аккумуляторе
+    pub fn active_inputs(&self, game_data: &GameData) -> Vec<usize> {
+        let mut inputs: Vec<usize> = [Side::White, Side::Black]
+            .into_iter()
+            .flat_map(|side| {
+                game_data
+                    .pieces(side)
+                    .iter()
+                    .filter_map(move |(position, piece)| {
+                        self.position_input(game_data, *position, side, *piece)
+                    })
+            })
+            .collect();
+        inputs.sort_unstable();
+        if game_data.current_move == Side::Black {
+            inputs.push(self.side_input());
+        }
+        inputs
+    }
+
+    /// Вычисляет аккумулятор по номерам активных входов
+    pub fn accumulate(&self, inputs: &[usize]) -> Accumulator {
+        let mut accumulator = Accumulator {
+            values: self.hidden_biases.clone(),
+        };
+        for &input in inputs {
+            self.add_input(&mut accumulator, input);
+        }
+        accumulator
+    }
+
+    pub fn add_input(&self, accumulator: &mut Accumulator, input: usize) {
+        let weights = &self.input_weights[input * self.hidden..(input + 1) * self.hidden];
+        for (value, weight) in accumulator.values.iter_mut().zip(weights) {
+            *value += weight;
+        }
+    }
+
+    pub fn remove_input(&self, accumulator: &mut Accumulator, input: usize) {
+        let weights = &self.input_weights[input * self.hidden..(input + 1) * self.hidden];
+        for (value, weight) in accumulator.values.iter_mut().zip(weights) {
+            *value -= weight;
+        }
+    }
+
+    /// Функция активации скрытого слоя
+    pub fn activation(value: f32) -> f32 {
+        value.clamp(0.0, 1.0)
+    }
+
+    /// Выход сети для аккумулятора: оценка с точки зрения белых в единицах [Network::OUTPUT_SCALE]
+    pub fn output(&self, accumulator: &Accumulator) -> f32 {
+        self.output_bias
+            + accumulator
+                .values
+                .iter()
+                .zip(&self.output_weights)
+                .map(|(value, weight)| Self::activation(*value) * weight)
+                .sum::<f32>()
+    }
+
+    /// Обновляет аккумулятор после хода piece_move из позиции before, приведшего в позицию after
+    pub fn update(
+        &self,
+        accumulator: &mut Accumulator,
+        before: &GameData,
+        piece_move: &Move,
+        after: &GameData,
+    ) {
+        let side = before.current_move;
+        if let Some(&piece) = before.pieces(side).get(&piece_move.from) {
+            if let Some(input) = self.position_input(before, piece_move.from, side, piece) {
+                self.remove_input(accumulator, input);
+            }
+            let piece = if piece_move.promotion {
+                Piece::King
+            } else {
+                piece
+            };
+            if let Some(input) = self.position_input(before, piece_move.to(), side, piece) {
+                self.add_input(accumulator, input);
+            }
+        }
+        let opponent = side.opposite();
+        for position in &piece_move.taken_pieces_positions {
+            if let Some(&piece) = before.pieces(opponent).get(position) {
+                if let Some(input) = self.position_input(before, *position, opponent, piece) {
+                    self.remove_input(accumulator, input);
+                }
+            }
+        }
+        match (before.current_move, after.current_move) {
+            (Side::White, Side::Black) => self.add_input(accumulator, self.side_input()),
+            (Side::Black, Side::White) => self.remove_input(accumulator, self.side_input()),
+            _ => {}
+        }
+    }
+
+    /// Загружает сеть, обученную для указанной разновидности игры
+    pub fn load(path: impl AsRef<Path>, variant: Variant) -> Result<Self, NetworkError> {
+        Self::read_from(&mut BufReader::new(File::open(path)?), variant)
+    }
+
+    /// Сохраняет сеть в файл для указанной разновидности игры
+    pub fn save(&self, path: impl AsRef<Path>, variant: &Variant) -> Result<(), NetworkError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer, variant)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Имя файла сети для данной разновидности игры
+    pub fn default_file_name(variant: &Variant) -> String {
+        variant.file_name("cknn")
+    }
+
+    /// Записывает сеть в двоичном формате
+    ///
+    /// Заголовок: "CKNN", версия, длина и название правил, количество игровых ячеек и размер
+    /// скрытого слоя. Затем веса входов, смещения скрытого слоя, веса и смещение выхода (f32)
+    pub fn write_to(&self, writer: &mut impl Write, variant: &Variant) -> io::Result<()> {
+        let name = variant.to_string();
+        writer.write_all(Self::MAGIC)?;
+        writer.write_all(&[Self::VERSION])?;
+        writer.write_all(&(name.len() as u16).to_le_bytes())?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&(self.squares as u16).to_le_bytes())?;
+        writer.write_all(&(self.hidden as u16).to_le_bytes())?;
+        for value in self
+            .input_weights
+            .iter()
+            .chain(&self.hidden_biases)
+            .chain(&self.output_weights)
+            .chain([&self.output_bias])
+        {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Читает сеть, записанную [Network::write_to]
+    pub fn read_from(reader: &mut impl Read, variant: Variant) -> Result<Self, NetworkError> {
+        let invalid = |reason: &str| NetworkError::InvalidFormat(reason.to_string());
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(invalid("неизвестный формат файла"));
+        }
+        let [version] = read_bytes(reader)?;
+        if version != Self::VERSION {
+            return Err(invalid("неподдерживаемая версия формата"));
+        }
+        let name_len = u16::from_le_bytes(read_bytes(reader)?);
+        let mut name = vec![0; name_len as usize];
+        reader.read_exact(&mut name)?;
+        let name = String::from_utf8(name).map_err(|_| invalid("название правил не в UTF-8"))?;
+        if name != variant.to_string() {
+            return Err(NetworkError::VariantMismatch {
+                expected: variant.to_string(),
+                found: name,
+            });
+        }
+        let squares = u16::from_le_bytes(read_bytes(reader)?) as usize;
+        let hidden = u16::from_le_bytes(read_bytes(reader)?) as usize;
+        if squares != GameData::new(variant).playable_positions().count() {
+            return Err(invalid("количество ячеек не совпадает с правилами"));
+        }
+
+        let mut network = Self::new(squares, hidden);
+        for value in network
+            .input_weights
+            .iter_mut()
+            .chain(&mut network.hidden_biases)
+            .chain(&mut network.output_weights)
+            .chain([&mut network.output_bias])
+        {
+            *value = f32::from_le_bytes(read_bytes(reader)?);
+        }
+        Ok(network)
+    }
+
+    /// Номер входа для фигуры на позиции, None - если позиция не является игровой ячейкой
+    /// этой сети
+    fn position_input(
+        &self,
+        game_data: &GameData,
+        position: Position,
+        side: Side,
+        piece: Piece,
+    ) -> Option<usize> {
+        let square = game_data.square_number(position)?;
+        (square <= self.squares).then(|| Self::piece_input(square, side, piece))
+    }
+}
+
+/// Оценка позиции нейросетью
+#[derive(Debug, Clone)]
+pub struct NetworkEvaluator {
+    pub network: Arc<Network>,
+}
+
+impl NetworkEvaluator {
+    pub fn new(network: Arc<Network>) -> Self {
+        Self { network }
+    }
+
+    /// Переводит выход сети в оценку с точки зрения стороны, которая ходит
+    fn score(&self, output: f32, game_data: &GameData) -> i32 {
+        let score = (output * Network::OUTPUT_SCALE).round() as i32;
+        match game_data.current_move {
+            Side::White => score,
+            Side::Black => -score,
+        }
+    }
+}
+
+impl Evaluator for NetworkEvaluator {
+    fn evaluate(&self, game_data: &GameData) -> i32 {
+        let accumulator = self
+            .network
+            .accumulate(&self.network.active_inputs(game_data));
+        self.score(self.network.output(&accumulator), game_data)
+    }
+
+    fn new_accumulator(&self, game_data: &GameData) -> Option<Accumulator> {
+        Some(
+            self.network
+                .accumulate(&self.network.active_inputs(game_data)),
+        )
+    }
+
+    fn update_accumulator(
+        &self,
+        accumulator: &mut Accumulator,
+        before: &GameData,
+        piece_move: &Move,
+        after: &GameData,
+    ) {
+        self.network.update(accumulator, before, piece_move, after);
+    }
+
+    fn evaluate_accumulated(&self, accumulator: &Accumulator, game_data: &GameData) -> i32 {
+        self.score(self.network.output(accumulator), game_data)
+    }
+}
+
+/// Читает из потока массив байтов фиксированной длины
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
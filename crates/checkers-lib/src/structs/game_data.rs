@@ -0,0 +1,557 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::{FenError, Piece, PositionViolation, Side, Variant},
+    structs::{Move, MoveGenerator, Position},
+    traits::Ruleset,
+};
+
+/// Состояние партии: расположение фигур, очередь хода и правила игры
+///
+/// При десериализации позиция проверяется, см. [GameData::validate]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "UncheckedGameData")]
+pub struct GameData {
+    // TODO заменить HashMap на что-то более эффективное по памяти
+    pub white_pieces: HashMap<Position, Piece>,
+    pub black_pieces: HashMap<Position, Piece>,
+    /// Текущий ход стороны
+    pub current_move: Side,
+    /// Разновидность игры, по правилам которой идёт партия
+    pub variant: Variant,
+    /// Сколько раз подряд каждая из сторон перемещала дамки (без взятия)
+    #[serde(default)]
+    pub consecutive_king_moves: HashMap<Side, u32>,
+    /// Сколько ходов подряд сторона, которая сейчас ходит, сделает до передачи хода (фора)
+    #[serde(default)]
+    pub extra_moves: u32,
+}
+
+/// Состояние партии до проверки позиции, используется при десериализации
+#[derive(Deserialize)]
+struct UncheckedGameData {
+    white_pieces: HashMap<Position, Piece>,
+    black_pieces: HashMap<Position, Piece>,
+    current_move: Side,
+    variant: Variant,
+    #[serde(default)]
+    consecutive_king_moves: HashMap<Side, u32>,
+    #[serde(default)]
+    extra_moves: u32,
+}
+
+impl TryFrom<UncheckedGameData> for GameData {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedGameData) -> Result<Self, Self::Error> {
+        let game_data = Self {
+            white_pieces: unchecked.white_pieces,
+            black_pieces: unchecked.black_pieces,
+            current_move: unchecked.current_move,
+            variant: unchecked.variant,
+            consecutive_king_moves: unchecked.consecutive_king_moves,
+            extra_moves: unchecked.extra_moves,
+        };
+        game_data
+            .validate()
+            .map(|_| game_data)
+            .map_err(|violations| {
+                let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+                format!("Недопустимая позиция: {}", violations.join("; "))
+            })
+    }
+}
+
+impl Default for GameData {
+    fn default() -> Self {
+        Self::new(Variant::default())
+    }
+}
+
+impl GameData {
+    /// Создаёт партию в начальной позиции указанной разновидности игры
+    pub fn new(variant: Variant) -> Self {
+        let ruleset = variant.ruleset();
+        let initial_pieces = |side: Side| {
+            ruleset
+                .initial_positions(side)
+                .into_iter()
+                .map(|position| (position, Piece::default()))
+                .collect()
+        };
+        let white_pieces = initial_pieces(Side::White);
+        let black_pieces = initial_pieces(Side::Black);
+        let current_move = ruleset.first_move();
+        Self {
+            white_pieces,
+            black_pieces,
+            current_move,
+            variant,
+            consecutive_king_moves: HashMap::new(),
+            extra_moves: 0,
+        }
+    }
+
+    /// Создаёт партию из позиции, записанной в формате FEN, например "W:W21,22,K23:B1-12"
+    ///
+    /// Номера ячеек записываются в числовой нотации (см. [GameData::square_number]),
+    /// дамки отмечаются буквой K, допускаются диапазоны номеров
+    pub fn from_fen(variant: Variant, fen: &str) -> Result<Self, FenError> {
+        let mut game_data = Self::new(variant);
+        game_data.white_pieces.clear();
+        game_data.black_pieces.clear();
+
+        let fen = fen.trim().trim_matches('"').trim_end_matches('.');
+        let mut fields = fen.split(':');
+        let side_to_move = fields.next().unwrap_or_default().trim();
+        game_data.current_move = Self::parse_fen_side(side_to_move)
+            .ok_or_else(|| FenError::InvalidSideToMove(side_to_move.to_string()))?;
+
+        for field in fields {
+            let field = field.trim();
+            let side = field
+                .get(..1)
+                .and_then(Self::parse_fen_side)
+                .ok_or_else(|| FenError::InvalidPieceList(field.to_string()))?;
+            for item in field[1..]
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+            {
+                let (piece, squares) = match item.strip_prefix(['K', 'k']) {
+                    Some(squares) => (Piece::King, squares),
+                    None => (Piece::Man, item),
+                };
+                let invalid_square = || FenError::InvalidSquare(item.to_string());
+                let (first, last) = squares.split_once('-').unwrap_or((squares, squares));
+                let first: usize = first.trim().parse().map_err(|_| invalid_square())?;
+                let last: usize = last.trim().parse().map_err(|_| invalid_square())?;
+                for number in first..=last {
+                    let position = game_data
+                        .position_by_square_number(number)
+                        .ok_or_else(invalid_square)?;
+                    game_data.pieces_mut(side).insert(position, piece);
+                }
+            }
+        }
+        game_data.validate().map_err(FenError::IllegalPosition)?;
+        Ok(game_data)
+    }
+
+    /// Проверяет, что позиция может возникнуть в партии по правилам игры
+    ///
+    /// Возвращает все найденные нарушения. Используется перед тем, как принять позицию
+    /// из FEN, сохранения или редактора позиции. Позиция без фигур у одной из сторон
+    /// допустима: так заканчивается партия
+    pub fn validate(&self) -> Result<(), Vec<PositionViolation>> {
+        let mut violations = Vec::new();
+        let ruleset = self.ruleset();
+        for side in [Side::White, Side::Black] {
+            let pieces = self.pieces(side);
+            let mut positions: Vec<Position> = pieces.keys().copied().collect();
+            positions.sort_by_key(|position| (position.row, position.column));
+            for position in positions {
+                if !self.is_inside_board(position) {
+                    violations.push(PositionViolation::OutsideBoard { side, position });
+                } else if !ruleset.is_playable_cell(position) {
+                    violations.push(PositionViolation::UnplayableCell { side, position });
+                } else if pieces[&position].is_man() && self.is_promotion_row(side, position) {
+                    violations.push(PositionViolation::ManOnPromotionRow { side, position });
+                }
+                if side == Side::White && self.black_pieces.contains_key(&position) {
+                    violations.push(PositionViolation::OccupiedByBothSides(position));
+                }
+            }
+            let max = ruleset.initial_positions(side).len();
+            if pieces.len() > max {
+                violations.push(PositionViolation::TooManyPieces {
+                    side,
+                    count: pieces.len(),
+                    max,
+                });
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Проверяет, что с позиции можно начать партию: кроме проверок [GameData::validate],
+    /// у каждой стороны должна быть хотя бы одна фигура
+    pub fn validate_start(&self) -> Result<(), Vec<PositionViolation>> {
+        let mut violations = self.validate().err().unwrap_or_default();
+        for side in [Side::White, Side::Black] {
+            if self.pieces(side).is_empty() {
+                violations.push(PositionViolation::NoPieces(side));
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Возвращает позицию в формате FEN
+    pub fn to_fen(&self) -> String {
+        let pieces = |side: Side| {
+            let mut squares: Vec<(usize, Piece)> = self
+                .pieces(side)
+                .iter()
+                .filter_map(|(position, piece)| Some((self.square_number(*position)?, *piece)))
+                .collect();
+            squares.sort_by_key(|(number, _)| *number);
+            squares
+                .into_iter()
+                .map(|(number, piece)| match piece {
+                    Piece::Man => number.to_string(),
+                    Piece::King => format!("K{}", number),
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        format!(
+            "{}:W{}:B{}",
+            Self::fen_side(self.current_move),
+            pieces(Side::White),
+            pieces(Side::Black)
+        )
+    }
+
+    /// Возвращает хеш расстановки фигур и стороны, которая ходит (хеширование Зобриста)
+    ///
+    /// Хеш не зависит от порядка фигур в памяти и одинаков на всех платформах, поэтому
+    /// используется как ключ позиции в файлах, например в дебютной книге
+    pub fn position_hash(&self) -> u64 {
+        // Ключи Зобриста не хранятся в таблице, а вычисляются из номера ячейки и вида фигуры
+        let key = |value: u64| {
+            // SplitMix64
+            let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        let mut hash = match self.current_move {
+            Side::White => 0,
+            Side::Black => key(u64::MAX),
+        };
+        for side in [Side::White, Side::Black] {
+            for (position, piece) in self.pieces(side) {
+                let kind = match (side, piece) {
+                    (Side::White, Piece::Man) => 0,
+                    (Side::White, Piece::King) => 1,
+                    (Side::Black, Piece::Man) => 2,
+                    (Side::Black, Piece::King) => 3,
+                };
+                let square = self.square_number(*position).unwrap_or_default() as u64;
+                hash ^= key(square * 4 + kind);
+            }
+        }
+        hash
+    }
+
+    fn parse_fen_side(side: &str) -> Option<Side> {
+        match side {
+            "W" | "w" => Some(Side::White),
+            "B" | "b" => Some(Side::Black),
+            _ => None,
+        }
+    }
+
+    fn fen_side(side: Side) -> &'static str {
+        match side {
+            Side::White => "W",
+            Side::Black => "B",
+        }
+    }
+
+    /// Возвращает правила, по которым идёт партия
+    pub fn ruleset(&self) -> &dyn Ruleset {
+        self.variant.ruleset()
+    }
+
+    /// Возвращает размер игральной доски в ячейках
+    pub fn board_cells(&self) -> (i8, i8) {
+        self.ruleset().board_size()
+    }
+
+    /// Проверяет, закончена ли игра
+    ///
+    /// Игра заканчивается, когда у стороны, которая должна ходить, не осталось фигур или ходов
+    pub fn is_game_ended(&self) -> bool {
+        self.get_all_available_moves().is_empty()
+    }
+
+    /// Возвращает победившую сторону, если игра закончена
+    ///
+    /// Обычно проигрывает сторона, у которой не осталось фигур или ходов, а в игре "в поддавки" -
+    /// выигрывает
+    pub fn winner(&self) -> Option<Side> {
+        if !self.is_game_ended() {
+            return None;
+        }
+        if self.ruleset().is_giveaway() {
+            Some(self.current_move)
+        } else {
+            Some(self.current_move.opposite())
+        }
+    }
+
+    /// Проверяет, выполнено ли для фигуры условие того, что она превращается в дамку
+    pub fn is_turning_to_king_condition_satisfied(&self, side: Side, position: Position) -> bool {
+        let piece = self.pieces(side).get(&position).unwrap();
+        piece.is_man() && self.is_promotion_row(side, position)
+    }
+
+    /// Проверяет, находится ли ячейка на строке, дойдя до которой шашка данной стороны становится дамкой
+    pub fn is_promotion_row(&self, side: Side, position: Position) -> bool {
+        match side {
+            Side::White => position.row == 0,
+            Side::Black => position.row == self.board_cells().0 - 1,
+        }
+    }
+
+    /// Совершает ход за указанную сторону и передаёт ход противоположной стороне
+    pub fn make_move(&mut self, side: Side, piece_move: &Move) {
+        let is_king_movement =
+            !piece_move.is_taking() && self.pieces(side)[&piece_move.from].is_king();
+        let consecutive_king_moves = self.consecutive_king_moves.entry(side).or_default();
+        *consecutive_king_moves = if is_king_movement {
+            *consecutive_king_moves + 1
+        } else {
+            0
+        };
+
+        // Убираем все фишки на пути, так как они были "съедены" во время взятия
+        self.remove_pieces(&piece_move.taken_pieces_positions, side.opposite());
+        self.move_piece(side, piece_move.from, piece_move.to());
+        if piece_move.promotion {
+            self.turn_man_to_king(side, piece_move.to());
+        }
+        if self.extra_moves > 0 {
+            self.extra_moves -= 1;
+        } else {
+            self.pass_the_move();
+        }
+    }
+
+    /// Возвращает, сколько раз подряд указанная сторона перемещала дамки (без взятия)
+    pub fn consecutive_king_moves(&self, side: Side) -> u32 {
+        self.consecutive_king_moves
+            .get(&side)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Превращает шашку данной стороны на указанной позиции в дамку
+    pub fn turn_man_to_king(&mut self, side: Side, position: Position) {
+        self.pieces_mut(side).insert(position, Piece::King);
+    }
+
+    /// Возвращает итератор по координатам ячеек игральной доски
+    pub fn board_cell_coordinates(&self) -> impl Iterator<Item = (i8, i8)> {
+        use itertools::Itertools;
+        let (rows, columns) = self.board_cells();
+        (0..rows).cartesian_product(0..columns)
+    }
+
+    /// Проверяет, расположена ли ячейка с указанными координатами в пределах игральной доски
+    pub fn is_inside_board(&self, position: Position) -> bool {
+        let (rows, columns) = self.board_cells();
+        (0..rows).contains(&position.row) && (0..columns).contains(&position.column)
+    }
+
+    /// Удаляет фигуры указанной стороны из указанных позиций
+    pub fn remove_pieces(&mut self, positions: &[Position], side: Side) {
+        let pieces = self.pieces_mut(side);
+        for position in positions {
+            pieces.remove(position);
+        }
+    }
+
+    /// Передвигает фигуру из позиции from, в позицию to
+    pub fn move_piece(&mut self, side: Side, from: Position, to: Position) {
+        let pieces = self.pieces_mut(side);
+        let piece = pieces.remove(&from).unwrap();
+        pieces.insert(to, piece);
+    }
+
+    /// Передать ход противоположной стороне
+    pub fn pass_the_move(&mut self) {
+        self.current_move = self.current_move.opposite();
+    }
+
+    /// Возвращает все ходы, которые заканчиваются в данной ячейке
+    ///
+    /// Различные взятия могут заканчиваться в одной и той же ячейке, поэтому ходов может быть несколько
+    pub fn get_moves_ending_at_position(&self, moves: &[Move], position: Position) -> Vec<Move> {
+        moves
+            .iter()
+            .filter(|piece_move| piece_move.to() == position)
+            .cloned()
+            .collect()
+    }
+
+    /// Возвращает все ходы, доступные стороне, которая сейчас ходит
+    pub fn get_all_available_moves(&self) -> Vec<Move> {
+        MoveGenerator::new(self, self.ruleset()).get_available_moves(self.current_move)
+    }
+
+    /// Просчитывает возможные ходы для фигуры на указанной позиции
+    pub fn get_available_moves(&self, position: Position) -> Vec<Move> {
+        self.get_all_available_moves()
+            .into_iter()
+            .filter(|piece_move| piece_move.from == position)
+            .collect()
+    }
+
+    /// Возвращает номер ячейки в числовой нотации
+    ///
+    /// Игровые ячейки нумеруются с единицы по горизонталям, начиная с левой верхней.
+    /// Для английских и международных шашек это стандартная нотация
+    pub fn square_number(&self, position: Position) -> Option<usize> {
+        self.playable_positions()
+            .position(|playable_position| playable_position == position)
+            .map(|index| index + 1)
+    }
+
+    /// Возвращает ячейку по её номеру в числовой нотации
+    pub fn position_by_square_number(&self, number: usize) -> Option<Position> {
+        self.playable_positions().nth(number.checked_sub(1)?)
+    }
+
+    /// Возвращает запись хода в числовой нотации, например "11-15" или "15x22x31"
+    pub fn move_notation(&self, piece_move: &Move) -> String {
+        let separator = if piece_move.is_taking() { "x" } else { "-" };
+        let squares: Vec<Position> = if piece_move.is_taking() {
+            std::iter::once(piece_move.from)
+                .chain(piece_move.path.iter().copied())
+                .collect()
+        } else {
+            vec![piece_move.from, piece_move.to()]
+        };
+        squares
+            .into_iter()
+            .map(|position| {
+                self.square_number(position)
+                    .expect("ход не может проходить через неигровую ячейку")
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    /// Запись последовательности ходов, начиная с текущей позиции, например главного варианта
+    pub fn line_notation(&self, moves: &[Move]) -> Vec<String> {
+        let mut game_data = self.clone();
+        moves
+            .iter()
+            .map(|piece_move| {
+                let notation = game_data.move_notation(piece_move);
+                game_data.make_move(game_data.current_move, piece_move);
+                notation
+            })
+            .collect()
+    }
+
+    /// Находит доступный ход стороны, которая сейчас ходит, по записи в числовой нотации
+    ///
+    /// Взятие можно записать как полностью ("15x22x31"), так и только начальной и конечной
+    /// ячейками ("15x31" или "15-31"). Возвращает None, если ход недоступен или запись неоднозначна
+    pub fn parse_move(&self, notation: &str) -> Option<Move> {
+        let squares = notation
+            .trim()
+            .split(['-', 'x', 'X', ':'])
+            .map(|square| {
+                square
+                    .trim()
+                    .parse()
+                    .ok()
+                    .and_then(|number| self.position_by_square_number(number))
+            })
+            .collect::<Option<Vec<Position>>>()?;
+        let (from, landing_positions) = squares.split_first()?;
+        let to = *landing_positions.last()?;
+        let mut matching_moves = self
+            .get_all_available_moves()
+            .into_iter()
+            .filter(|piece_move| {
+                piece_move.from == *from
+                    && piece_move.to() == to
+                    && (landing_positions.len() == 1 || piece_move.path == landing_positions)
+            });
+        match (matching_moves.next(), matching_moves.next()) {
+            (Some(piece_move), None) => Some(piece_move),
+            _ => None,
+        }
+    }
+
+    /// Возвращает игровые ячейки доски по горизонталям, начиная с левой верхней
+    ///
+    /// Порядок совпадает с нумерацией ячеек, см. [GameData::square_number]
+    pub fn playable_positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.board_cell_coordinates()
+            .map(|(row, column)| Position { row, column })
+            .filter(|position| self.ruleset().is_playable_cell(*position))
+    }
+
+    /// Подсчитывает количество позиций, достижимых из текущей за указанное количество полуходов
+    ///
+    /// Используется для проверки корректности генератора ходов
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = self.get_all_available_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        moves
+            .iter()
+            .map(|piece_move| {
+                let mut game_data = self.clone();
+                game_data.make_move(self.current_move, piece_move);
+                game_data.perft(depth - 1)
+            })
+            .sum()
+    }
+
+    /// Проверяет, находится ли какая-нибудь фигура в ячейки с указанными координатами
+    pub fn is_cell_empty(&self, position: Position) -> bool {
+        !self.white_pieces.contains_key(&position) && !self.black_pieces.contains_key(&position)
+    }
+
+    /// Возвращает фигуры указанной стороны
+    pub fn pieces(&self, side: Side) -> &HashMap<Position, Piece> {
+        match side {
+            Side::White => &self.white_pieces,
+            Side::Black => &self.black_pieces,
+        }
+    }
+
+    fn pieces_mut(&mut self, side: Side) -> &mut HashMap<Position, Piece> {
+        match side {
+            Side::White => &mut self.white_pieces,
+            Side::Black => &mut self.black_pieces,
+        }
+    }
+}
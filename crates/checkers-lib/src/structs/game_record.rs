@@ -0,0 +1,287 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{GameResult, PdnError, Side, Variant},
+    structs::{GameData, Move, MoveAnalysis},
+};
+
+/// Запись партии: начальная позиция и сделанные ходы
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    /// Позиция, с которой началась партия
+    pub initial: GameData,
+    /// Ходы в порядке их совершения
+    pub moves: Vec<Move>,
+    /// Результат, объявленный без окончания партии на доске: сдача, ничья по соглашению
+    /// или результат из записи PDN
+    pub declared_result: Option<GameResult>,
+    /// Дополнительные теги PDN, например Event, White и Black. Теги GameType, FEN и Result
+    /// формируются по самой партии
+    pub tags: Vec<(String, String)>,
+}
+
+impl GameRecord {
+    /// Теги PDN, которые формируются по самой партии
+    const GENERATED_TAGS: [&'static str; 3] = ["GameType", "FEN", "Result"];
+
+    pub fn new(initial: GameData) -> Self {
+        Self {
+            initial,
+            moves: Vec::new(),
+            declared_result: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Разбирает партии, записанные в формате PDN. Сборник может содержать несколько партий
+    ///
+    /// Разновидность игры определяется по тегу GameType, а если его нет, используется
+    /// default_variant. Начальная позиция берётся из тега FEN. Комментарии, варианты в скобках
+    /// и оценочные знаки пропускаются
+    pub fn parse_pdn(default_variant: &Variant, pdn: &str) -> Vec<Result<Self, PdnError>> {
+        let mut games = Vec::new();
+        let mut tags: Vec<(String, String)> = Vec::new();
+        let mut moves: Vec<String> = Vec::new();
+        let mut finish = |tags: &mut Vec<(String, String)>,
+                          moves: &mut Vec<String>,
+                          result: Option<GameResult>| {
+            if !tags.is_empty() || !moves.is_empty() {
+                games.push(Self::from_pdn_parts(default_variant, tags, moves, result));
+            }
+            tags.clear();
+            moves.clear();
+        };
+
+        let mut chars = pdn.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '[' => {
+                    // Теги после ходов начинают следующую партию
+                    if !moves.is_empty() {
+                        finish(&mut tags, &mut moves, None);
+                    }
+                    let tag: String = chars.by_ref().take_while(|c| *c != ']').collect();
+                    let (name, value) = tag
+                        .trim()
+                        .split_once(char::is_whitespace)
+                        .unwrap_or((&tag, ""));
+                    tags.push((name.to_string(), value.trim().trim_matches('"').to_string()));
+                }
+                '{' => chars.by_ref().take_while(|c| *c != '}').for_each(drop),
+                ';' => chars.by_ref().take_while(|c| *c != '\n').for_each(drop),
+                '(' => {
+                    let mut depth = 1;
+                    for c in chars.by_ref() {
+                        match c {
+                            '(' => depth += 1,
+                            ')' => depth -= 1,
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                c if c.is_whitespace() => {}
+                c => {
+                    let mut word = String::from(c);
+                    while let Some(c) =
+                        chars.next_if(|c| !c.is_whitespace() && !"[{(;".contains(*c))
+                    {
+                        word.push(c);
+                    }
+                    if let Some(result) = GameResult::from_pdn(&word) {
+                        finish(&mut tags, &mut moves, Some(result));
+                        continue;
+                    }
+                    // Номер хода может быть записан слитно с ходом: "1.11-15"
+                    let notation = word
+                        .rsplit('.')
+                        .next()
+                        .unwrap_or_default()
+                        .trim_end_matches(['!', '?']);
+                    if !notation.is_empty() && !notation.starts_with('$') {
+                        moves.push(notation.to_string());
+                    }
+                }
+            }
+        }
+        finish(&mut tags, &mut moves, None);
+        games
+    }
+
+    /// Собирает партию из тегов и ходов, прочитанных из записи PDN
+    fn from_pdn_parts(
+        default_variant: &Variant,
+        tags: &[(String, String)],
+        moves: &[String],
+        result: Option<GameResult>,
+    ) -> Result<Self, PdnError> {
+        let tag = |name: &str| {
+            tags.iter()
+                .find(|(tag_name, _)| tag_name.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        };
+        let variant = match tag("GameType") {
+            Some(game_type) => game_type
+                .split(',')
+                .next()
+                .and_then(|number| number.trim().parse().ok())
+                .and_then(Variant::from_pdn_game_type)
+                .ok_or_else(|| PdnError::UnknownGameType(game_type.to_string()))?,
+            None => default_variant.clone(),
+        };
+        let initial = match tag("FEN") {
+            Some(fen) => GameData::from_fen(variant, fen).map_err(PdnError::InvalidFen)?,
+            None => GameData::new(variant),
+        };
+
+        let mut record = Self::new(initial.clone());
+        let mut game_data = initial;
+        for (index, notation) in moves.iter().enumerate() {
+            let piece_move =
+                game_data
+                    .parse_move(notation)
+                    .ok_or_else(|| PdnError::IllegalMove {
+                        ply: index + 1,
+                        notation: notation.clone(),
+                    })?;
+            game_data.make_move(game_data.current_move, &piece_move);
+            record.push(piece_move);
+        }
+        record.declared_result = result.or_else(|| tag("Result").and_then(GameResult::from_pdn));
+        record.tags = tags
+            .iter()
+            .filter(|(name, _)| !Self::GENERATED_TAGS.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        Ok(record)
+    }
+
+    /// Добавляет ход в запись партии
+    pub fn push(&mut self, piece_move: Move) {
+        self.moves.push(piece_move);
+    }
+
+    /// Возвращает позиции перед каждым ходом и позицию после последнего хода
+    pub fn positions(&self) -> Vec<GameData> {
+        let mut positions = vec![self.initial.clone()];
+        for piece_move in &self.moves {
+            let mut game_data = positions
+                .last()
+                .expect("начальная позиция есть всегда")
+                .clone();
+            let side = game_data.current_move;
+            game_data.make_move(side, piece_move);
+            positions.push(game_data);
+        }
+        positions
+    }
+
+    /// Возвращает ходы партии в числовой нотации
+    pub fn notation(&self) -> Vec<String> {
+        self.positions()
+            .iter()
+            .zip(&self.moves)
+            .map(|(game_data, piece_move)| game_data.move_notation(piece_move))
+            .collect()
+    }
+
+    /// Возвращает номера ходов: номер ставится перед ходом стороны, которая по правилам ходит первой
+    ///
+    /// Если партия начинается ходом другой стороны, перед первым ходом ставится номер с многоточием
+    pub fn move_numbers(&self) -> Vec<Option<String>> {
+        let first_side = self.initial.ruleset().first_move();
+        let mut number = 0;
+        let mut previous_side: Option<Side> = None;
+        self.positions()
+            .iter()
+            .take(self.moves.len())
+            .map(|game_data| {
+                let side = game_data.current_move;
+                let label = if side == first_side && previous_side != Some(first_side) {
+                    number += 1;
+                    Some(format!("{}.", number))
+                } else if previous_side.is_none() {
+                    number += 1;
+                    Some(format!("{}...", number))
+                } else {
+                    None
+                };
+                previous_side = Some(side);
+                label
+            })
+            .collect()
+    }
+
+    /// Записывает партию в формате PDN
+    ///
+    /// Если передан анализ партии, оценённые ходы отмечаются знаками "?!", "?" и "??",
+    /// а в комментариях указываются потеря в оценке и лучший ход
+    pub fn to_pdn(&self, analysis: &[MoveAnalysis]) -> String {
+        let mut pdn = String::new();
+        for (name, value) in &self.tags {
+            pdn.push_str(&format!("[{} \"{}\"]\n", name, value.replace('"', "'")));
+        }
+        if let Some(game_type) = self.initial.variant.pdn_game_type() {
+            pdn.push_str(&format!("[GameType \"{}\"]\n", game_type));
+        }
+        if self.initial.to_fen() != GameData::new(self.initial.variant.clone()).to_fen() {
+            pdn.push_str(&format!("[FEN \"{}\"]\n", self.initial.to_fen()));
+        }
+        let result = self.result().pdn();
+        pdn.push_str(&format!("[Result \"{}\"]\n\n", result));
+
+        let mut tokens = Vec::new();
+        for (index, (notation, number)) in self
+            .notation()
+            .into_iter()
+            .zip(self.move_numbers())
+            .enumerate()
+        {
+            if let Some(number) = number {
+                tokens.push(number);
+            }
+            let move_analysis = analysis.get(index);
+            let judgement = move_analysis.and_then(|move_analysis| move_analysis.judgement);
+            tokens.push(format!(
+                "{}{}",
+                notation,
+                judgement
+                    .map(|judgement| judgement.symbol())
+                    .unwrap_or_default()
+            ));
+            if let (Some(move_analysis), Some(judgement)) = (move_analysis, judgement) {
+                tokens.push(format!("{{{}}}", move_analysis.comment(judgement)));
+            }
+        }
+        tokens.push(result.to_string());
+        pdn.push_str(&tokens.join(" "));
+        pdn.push('\n');
+        pdn
+    }
+
+    /// Возвращает результат партии: по позиции на доске, если партия окончена,
+    /// иначе объявленный результат
+    pub fn result(&self) -> GameResult {
+        let positions = self.positions();
+        let last = positions.last().expect("начальная позиция есть всегда");
+        match last.winner() {
+            Some(winner) => GameResult::win(winner),
+            None => self.declared_result.unwrap_or(GameResult::Unfinished),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::{Piece, Side},
+    structs::{GameData, Position},
+};
+
+/// Фора, которую сильный игрок даёт слабому
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Handicap {
+    /// Сторона, за которую играет сильный игрок
+    pub stronger_side: Side,
+    /// Сколько шашек убирается у сильного игрока
+    pub removed_men: u8,
+    /// Начинает ли слабый игрок с дамкой вместо одной из шашек
+    pub weaker_side_king: bool,
+    /// Сколько ходов слабый игрок делает подряд в начале партии
+    pub extra_moves: u8,
+}
+
+impl Handicap {
+    /// Является ли фора пустой, то есть партия начинается как обычно
+    pub fn is_empty(&self) -> bool {
+        self.removed_men == 0 && !self.weaker_side_king && self.extra_moves == 0
+    }
+
+    /// Применяет фору к партии в начальной позиции
+    ///
+    /// Шашки сильного игрока убираются, начиная с его первой горизонтали; в дамку превращается
+    /// шашка слабого игрока на его первой горизонтали. При дополнительных ходах партию начинает слабый игрок
+    pub fn apply(&self, game_data: &mut GameData) {
+        let weaker_side = self.stronger_side.opposite();
+        let removed_positions: Vec<Position> =
+            Self::men_from_back_rank(game_data, self.stronger_side)
+                .into_iter()
+                .take(self.removed_men as usize)
+                .collect();
+        game_data.remove_pieces(&removed_positions, self.stronger_side);
+        if self.weaker_side_king {
+            if let Some(position) = Self::men_from_back_rank(game_data, weaker_side).first() {
+                game_data.turn_man_to_king(weaker_side, *position);
+            }
+        }
+        if self.extra_moves > 0 {
+            game_data.current_move = weaker_side;
+            game_data.extra_moves = self.extra_moves as u32;
+        }
+    }
+
+    /// Возвращает шашки стороны, начиная с её первой горизонтали, слева направо
+    fn men_from_back_rank(game_data: &GameData, side: Side) -> Vec<Position> {
+        let (rows, _) = game_data.board_cells();
+        let mut positions: Vec<Position> = game_data
+            .pieces(side)
+            .iter()
+            .filter(|(_, piece)| **piece == Piece::Man)
+            .map(|(position, _)| *position)
+            .collect();
+        positions.sort_by_key(|position| {
+            // Расстояние от первой горизонтали стороны
+            let distance = match side {
+                Side::White => rows - 1 - position.row,
+                Side::Black => position.row,
+            };
+            (distance, position.column)
+        });
+        positions
+    }
+}
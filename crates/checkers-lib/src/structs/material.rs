@@ -0,0 +1,105 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use derive_more::Display;
+
+use crate::{
+    enums::{Piece, Side},
+    structs::GameData,
+};
+
+/// Состав фигур на доске: количество шашек и дамок каждой из сторон
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[display(
+    fmt = "белые {}+{}, чёрные {}+{}",
+    white_men,
+    white_kings,
+    black_men,
+    black_kings
+)]
+pub struct Material {
+    pub white_men: u8,
+    pub white_kings: u8,
+    pub black_men: u8,
+    pub black_kings: u8,
+}
+
+impl Material {
+    /// Возвращает состав фигур в позиции
+    pub fn of(game_data: &GameData) -> Self {
+        let count = |side: Side, piece: Piece| {
+            game_data
+                .pieces(side)
+                .values()
+                .filter(|side_piece| **side_piece == piece)
+                .count() as u8
+        };
+        Self {
+            white_men: count(Side::White, Piece::Man),
+            white_kings: count(Side::White, Piece::King),
+            black_men: count(Side::Black, Piece::Man),
+            black_kings: count(Side::Black, Piece::King),
+        }
+    }
+
+    /// Возвращает количество фигур указанного вида у указанной стороны
+    pub fn count(&self, side: Side, piece: Piece) -> u8 {
+        match (side, piece) {
+            (Side::White, Piece::Man) => self.white_men,
+            (Side::White, Piece::King) => self.white_kings,
+            (Side::Black, Piece::Man) => self.black_men,
+            (Side::Black, Piece::King) => self.black_kings,
+        }
+    }
+
+    /// Общее количество фигур на доске
+    pub fn pieces(&self) -> u32 {
+        self.men() + self.white_kings as u32 + self.black_kings as u32
+    }
+
+    /// Общее количество шашек на доске
+    pub fn men(&self) -> u32 {
+        self.white_men as u32 + self.black_men as u32
+    }
+
+    /// Возвращает все составы, в которых от 1 до max_pieces фигур
+    ///
+    /// Составы упорядочены так, что взятие или превращение шашки в дамку всегда приводит
+    /// к составу, который находится раньше: сначала по количеству фигур, затем по количеству шашек
+    pub fn up_to(max_pieces: u32) -> Vec<Self> {
+        let max = max_pieces as u8;
+        let mut materials = Vec::new();
+        for white_men in 0..=max {
+            for white_kings in 0..=max - white_men {
+                for black_men in 0..=max - white_men - white_kings {
+                    for black_kings in 0..=max - white_men - white_kings - black_men {
+                        let material = Self {
+                            white_men,
+                            white_kings,
+                            black_men,
+                            black_kings,
+                        };
+                        if material.pieces() > 0 {
+                            materials.push(material);
+                        }
+                    }
+                }
+            }
+        }
+        materials.sort_by_key(|material| (material.pieces(), material.men()));
+        materials
+    }
+}
@@ -1,3 +1,31 @@
+mod ballot;
+mod book_move;
+mod engine;
+pub mod evaluators;
+mod game_data;
+mod game_record;
+mod handicap;
+mod material;
+mod move_analysis;
+mod move_generator;
+mod opening_book;
+mod piece_move;
 mod position;
+pub mod rulesets;
+mod tablebase;
 
-pub use self::position::Position;
+pub use self::{
+    ballot::{Ballot, BallotDeck},
+    book_move::BookMove,
+    engine::{Engine, SearchResult},
+    game_data::GameData,
+    game_record::GameRecord,
+    handicap::Handicap,
+    material::Material,
+    move_analysis::MoveAnalysis,
+    move_generator::MoveGenerator,
+    opening_book::OpeningBook,
+    piece_move::Move,
+    position::Position,
+    tablebase::Tablebase,
+};
@@ -0,0 +1,57 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{MoveJudgement, Side},
+    structs::Move,
+};
+
+/// Результат анализа одного хода партии
+#[derive(Debug, Clone)]
+pub struct MoveAnalysis {
+    /// Сторона, сделавшая ход
+    pub side: Side,
+    /// Сделанный ход
+    pub played_move: Move,
+    /// Сделанный ход в числовой нотации
+    pub played_notation: String,
+    /// Лучший ход по мнению движка
+    pub best_move: Option<Move>,
+    /// Лучший ход в числовой нотации
+    pub best_notation: Option<String>,
+    /// Оценка позиции перед ходом при лучшей игре, с точки зрения стороны, сделавшей ход
+    pub best_score: i32,
+    /// Оценка позиции после сделанного хода, с точки зрения стороны, сделавшей ход
+    pub played_score: i32,
+    /// Оценка хода, если он заметно хуже лучшего
+    pub judgement: Option<MoveJudgement>,
+}
+
+impl MoveAnalysis {
+    /// Насколько сделанный ход хуже лучшего (в сотых долях шашки)
+    pub fn delta(&self) -> i32 {
+        (self.best_score - self.played_score).max(0)
+    }
+
+    /// Комментарий к ходу: оценка хода, потеря в оценке и лучший ход
+    pub fn comment(&self, judgement: MoveJudgement) -> String {
+        match &self.best_notation {
+            Some(best_notation) => {
+                format!("{}: -{}, лучше {}", judgement, self.delta(), best_notation)
+            }
+            None => format!("{}: -{}", judgement, self.delta()),
+        }
+    }
+}
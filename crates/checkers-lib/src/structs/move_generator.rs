@@ -0,0 +1,344 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{Direction, KingRange, Piece, Promotion, Side},
+    structs::{GameData, Move, Position},
+    traits::Ruleset,
+};
+
+/// Генератор ходов, параметризованный правилами игры
+pub struct MoveGenerator<'a, R: Ruleset + ?Sized> {
+    game_data: &'a GameData,
+    ruleset: &'a R,
+}
+
+/// Возможность взять фигуру противника в одном из направлений
+struct TakingOpportunity {
+    /// Позиция фигуры противника, которую можно взять
+    enemy_piece_position: Position,
+    /// Ячейки, на которые можно встать после взятия
+    landing_positions: Vec<Position>,
+}
+
+impl<'a, R: Ruleset + ?Sized> MoveGenerator<'a, R> {
+    pub fn new(game_data: &'a GameData, ruleset: &'a R) -> Self {
+        Self { game_data, ruleset }
+    }
+
+    /// Возвращает все ходы, которые может совершить указанная сторона
+    ///
+    /// Учитывает обязательность взятия и правило большинства
+    pub fn get_available_moves(&self, side: Side) -> Vec<Move> {
+        use itertools::Itertools;
+        let pieces: Vec<(Position, Piece)> = self
+            .game_data
+            .pieces(side)
+            .iter()
+            .map(|(position, piece)| (*position, *piece))
+            // Порядок ходов не должен зависеть от порядка обхода HashMap
+            .sorted_by_key(|(position, _)| (position.row, position.column))
+            .collect();
+
+        let takings: Vec<Move> = pieces
+            .iter()
+            .flat_map(|(position, piece)| self.get_taking_moves(*position, *piece, side))
+            .collect();
+        let takings = self.ruleset.select_takings(self.game_data, side, takings);
+        if !takings.is_empty() && self.ruleset.is_taking_mandatory() {
+            return takings;
+        }
+
+        let is_king_movement_restricted = self.is_king_movement_restricted(side);
+        let mut moves: Vec<Move> = pieces
+            .iter()
+            .filter(|(_, piece)| !(is_king_movement_restricted && piece.is_king()))
+            .flat_map(|(position, piece)| self.get_movement_moves(*position, *piece, side))
+            .collect();
+        moves.extend(takings);
+        moves
+    }
+
+    /// Проверяет, запрещено ли стороне перемещать дамки (без взятия) из-за ограничения
+    /// на количество ходов дамками подряд
+    ///
+    /// Ограничение действует, только если у стороны есть и дамки, и шашки
+    fn is_king_movement_restricted(&self, side: Side) -> bool {
+        let Some(max_consecutive_king_moves) = self.ruleset.max_consecutive_king_moves() else {
+            return false;
+        };
+        let pieces = self.game_data.pieces(side);
+        pieces.values().any(Piece::is_man)
+            && pieces.values().any(Piece::is_king)
+            && self.game_data.consecutive_king_moves(side) >= max_consecutive_king_moves
+    }
+
+    /// Возвращает перемещения (без взятия) фигуры на указанной позиции
+    fn get_movement_moves(&self, position: Position, piece: Piece, side: Side) -> Vec<Move> {
+        let mut destinations = Vec::new();
+        match piece {
+            Piece::Man => {
+                for direction in self.ruleset.man_movement_directions(side) {
+                    let destination = self.step(position, direction);
+                    if self.is_cell_free(destination, position) {
+                        destinations.push(destination);
+                    }
+                }
+            }
+            Piece::King => {
+                for direction in self.ruleset.king_directions() {
+                    let mut destination = self.step(position, direction);
+                    // Дамка перемещается до первой занятой ячейки или до края доски
+                    while self.is_cell_free(destination, position) {
+                        destinations.push(destination);
+                        if self.ruleset.king_range() == KingRange::Short {
+                            break;
+                        }
+                        destination = self.step(destination, direction);
+                    }
+                }
+            }
+        }
+        destinations
+            .into_iter()
+            .map(|destination| {
+                Move::movement(
+                    position,
+                    destination,
+                    piece.is_man() && self.game_data.is_promotion_row(side, destination),
+                )
+            })
+            .collect()
+    }
+
+    /// Возвращает завершённые последовательности взятий фигуры на указанной позиции
+    fn get_taking_moves(&self, position: Position, piece: Piece, side: Side) -> Vec<Move> {
+        let mut taking_moves: Vec<Move> = Vec::new();
+        self.get_taking_moves_rec(position, side, position, piece, &[], &[], &mut taking_moves);
+        Self::deduplicate_taking_moves(taking_moves)
+    }
+
+    /// Рекурсивно строит последовательности взятий
+    ///
+    /// В результат попадают только завершённые последовательности, т.е. такие, после которых
+    /// взять ещё одну фигуру противника уже невозможно
+    #[allow(clippy::too_many_arguments)]
+    fn get_taking_moves_rec(
+        &self,
+        initial_position: Position,
+        side: Side,
+        position: Position,
+        piece: Piece,
+        path: &[Position],
+        taken_pieces_positions: &[Position],
+        taking_moves: &mut Vec<Move>,
+    ) {
+        let opportunities = self.get_taking_opportunities(
+            initial_position,
+            side,
+            position,
+            piece,
+            taken_pieces_positions,
+        );
+
+        // Взятие больше невозможно, последовательность завершена
+        if opportunities.is_empty() {
+            if !path.is_empty() {
+                taking_moves.push(Move::taking(
+                    initial_position,
+                    Vec::from(path),
+                    Vec::from(taken_pieces_positions),
+                    self.is_promoted_by_taking(initial_position, side, path),
+                ));
+            }
+            return;
+        }
+
+        for opportunity in opportunities {
+            let mut enemy_pieces_positions = Vec::from(taken_pieces_positions);
+            enemy_pieces_positions.push(opportunity.enemy_piece_position);
+
+            let next_piece = |landing_position: Position| {
+                if piece.is_man()
+                    && self.ruleset.promotion() == Promotion::ContinuesMove
+                    && self.game_data.is_promotion_row(side, landing_position)
+                {
+                    Piece::King
+                } else {
+                    piece
+                }
+            };
+            // Если с каких-то ячеек после взятия можно продолжить взятие, то вставать можно только на них
+            let continuing_landing_positions: Vec<Position> = opportunity
+                .landing_positions
+                .iter()
+                .copied()
+                .filter(|landing_position| {
+                    !self
+                        .get_taking_opportunities(
+                            initial_position,
+                            side,
+                            *landing_position,
+                            next_piece(*landing_position),
+                            &enemy_pieces_positions,
+                        )
+                        .is_empty()
+                })
+                .collect();
+            let landing_positions = if continuing_landing_positions.is_empty() {
+                opportunity.landing_positions
+            } else {
+                continuing_landing_positions
+            };
+
+            for landing_position in landing_positions {
+                let mut move_path = Vec::from(path);
+                move_path.push(landing_position);
+                self.get_taking_moves_rec(
+                    initial_position,
+                    side,
+                    landing_position,
+                    next_piece(landing_position),
+                    &move_path,
+                    &enemy_pieces_positions,
+                    taking_moves,
+                );
+            }
+        }
+    }
+
+    /// Возвращает фигуры противника, которые можно взять с указанной позиции, и ячейки, на которые
+    /// можно встать после их взятия
+    fn get_taking_opportunities(
+        &self,
+        initial_position: Position,
+        side: Side,
+        position: Position,
+        piece: Piece,
+        taken_pieces_positions: &[Position],
+    ) -> Vec<TakingOpportunity> {
+        let (directions, range) = match piece {
+            Piece::Man => (self.ruleset.man_taking_directions(side), KingRange::Short),
+            Piece::King => (
+                self.ruleset.king_taking_directions(),
+                self.ruleset.king_range(),
+            ),
+        };
+
+        let mut opportunities = Vec::new();
+        for direction in directions {
+            let mut enemy_piece_position = self.step(position, direction);
+            if range == KingRange::Flying {
+                while self.is_cell_free(enemy_piece_position, initial_position) {
+                    enemy_piece_position = self.step(enemy_piece_position, direction);
+                }
+            }
+            // Уже взятые за данный "проход" фигуры остаются на доске до конца хода и не могут быть взяты повторно
+            if taken_pieces_positions.contains(&enemy_piece_position)
+                || !self.contains_takable_enemy_piece(enemy_piece_position, side, piece)
+            {
+                continue;
+            }
+
+            let mut landing_positions = Vec::new();
+            let mut landing_position = self.step(enemy_piece_position, direction);
+            while self.is_cell_free(landing_position, initial_position) {
+                landing_positions.push(landing_position);
+                if range == KingRange::Short {
+                    break;
+                }
+                landing_position = self.step(landing_position, direction);
+            }
+            if !landing_positions.is_empty() {
+                opportunities.push(TakingOpportunity {
+                    enemy_piece_position,
+                    landing_positions,
+                });
+            }
+        }
+        opportunities
+    }
+
+    /// Проверяет, превращается ли шашка в дамку в результате взятия по указанному пути
+    fn is_promoted_by_taking(
+        &self,
+        initial_position: Position,
+        side: Side,
+        path: &[Position],
+    ) -> bool {
+        let is_man = self
+            .game_data
+            .pieces(side)
+            .get(&initial_position)
+            .is_some_and(Piece::is_man);
+        let is_promotion_row =
+            |position: &Position| self.game_data.is_promotion_row(side, *position);
+        is_man
+            && match self.ruleset.promotion() {
+                Promotion::ContinuesMove => path.iter().any(is_promotion_row),
+                Promotion::AtEndOfMove => path.last().is_some_and(is_promotion_row),
+            }
+    }
+
+    /// Убирает эквивалентные взятия: с одной и той же конечной ячейкой и одним и тем же набором
+    /// взятых фигур, полученным в другом порядке
+    fn deduplicate_taking_moves(taking_moves: Vec<Move>) -> Vec<Move> {
+        use itertools::Itertools;
+        taking_moves
+            .into_iter()
+            .unique_by(|piece_move| {
+                (
+                    piece_move.to(),
+                    piece_move
+                        .taken_pieces_positions
+                        .iter()
+                        .map(|position| (position.row, position.column))
+                        .sorted()
+                        .collect::<Vec<(i8, i8)>>(),
+                )
+            })
+            .collect()
+    }
+
+    /// Возвращает следующую ячейку в указанном направлении, на которой может стоять фигура
+    ///
+    /// Ячейки, на которых фигуры стоять не могут, пропускаются. Так, на доске с чередующимися
+    /// цветами ортогональный шаг перемещает фигуру сразу на две клетки
+    fn step(&self, position: Position, direction: Direction) -> Position {
+        let mut next_position = position.next(direction);
+        while self.game_data.is_inside_board(next_position)
+            && !self.ruleset.is_playable_cell(next_position)
+        {
+            next_position = next_position.next(direction);
+        }
+        next_position
+    }
+
+    /// Проверяет, может ли фигура, начавшая ход в initial_position, встать на указанную ячейку
+    ///
+    /// Ячейка, с которой фигура начала ход, считается свободной, так как фигура её уже покинула
+    fn is_cell_free(&self, position: Position, initial_position: Position) -> bool {
+        self.game_data.is_inside_board(position)
+            && (position == initial_position || self.game_data.is_cell_empty(position))
+    }
+
+    /// Проверяет, содержит ли ячейка фигуру противника, которую указанная фигура может взять по правилам
+    fn contains_takable_enemy_piece(&self, position: Position, side: Side, piece: Piece) -> bool {
+        self.game_data
+            .pieces(side.opposite())
+            .get(&position)
+            .is_some_and(|enemy_piece| self.ruleset.can_take(piece, *enemy_piece))
+    }
+}
@@ -0,0 +1,237 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{
+    enums::{OpeningBookError, Variant},
+    structs::{BookMove, GameData, GameRecord, Move},
+};
+
+/// Дебютная книга: ходы, сделанные в начальных позициях сыгранных партий, со статистикой
+///
+/// Позиции хранятся по хешу (см. [GameData::position_hash]), ходы - в числовой нотации
+pub struct OpeningBook {
+    variant: Variant,
+    positions: HashMap<u64, Vec<BookMove>>,
+}
+
+impl OpeningBook {
+    /// Первые байты файла с книгой
+    const MAGIC: &'static [u8; 4] = b"CKBK";
+    /// Версия формата файла
+    const VERSION: u8 = 1;
+
+    /// Создаёт пустую книгу для указанной разновидности игры
+    pub fn new(variant: Variant) -> Self {
+        Self {
+            variant,
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Загружает книгу, составленную для указанной разновидности игры
+    pub fn load(path: impl AsRef<Path>, variant: Variant) -> Result<Self, OpeningBookError> {
+        Self::read_from(&mut BufReader::new(File::open(path)?), variant)
+    }
+
+    /// Сохраняет книгу в файл
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), OpeningBookError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Имя файла с книгой для указанной разновидности игры
+    pub fn default_file_name(variant: &Variant) -> String {
+        variant.file_name("ckbk")
+    }
+
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    /// Количество позиций в книге
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Добавляет в книгу первые max_plies ходов партии
+    ///
+    /// Партии других разновидностей игры пропускаются, возвращает true, если партия добавлена
+    pub fn add_game(&mut self, record: &GameRecord, max_plies: usize) -> bool {
+        if record.initial.variant != self.variant {
+            return false;
+        }
+        let result = record.result();
+        for (game_data, piece_move) in record.positions().iter().zip(&record.moves).take(max_plies)
+        {
+            let notation = game_data.move_notation(piece_move);
+            let moves = self.positions.entry(game_data.position_hash()).or_default();
+            let index = match moves
+                .iter()
+                .position(|book_move| book_move.notation == notation)
+            {
+                Some(index) => index,
+                None => {
+                    moves.push(BookMove {
+                        notation,
+                        ..BookMove::default()
+                    });
+                    moves.len() - 1
+                }
+            };
+            moves[index].add_game(game_data.current_move, result);
+        }
+        true
+    }
+
+    /// Возвращает ходы книги в позиции, начиная с самых частых
+    pub fn moves(&self, game_data: &GameData) -> Vec<BookMove> {
+        if game_data.variant != self.variant {
+            return Vec::new();
+        }
+        let mut moves = self
+            .positions
+            .get(&game_data.position_hash())
+            .cloned()
+            .unwrap_or_default();
+        moves.sort_by(|a, b| b.games.cmp(&a.games).then(b.weight.cmp(&a.weight)));
+        moves
+    }
+
+    /// Возвращает ход книги с наибольшим весом. None - если позиции нет в книге
+    /// или все её ходы проигрывали
+    pub fn best_move(&self, game_data: &GameData) -> Option<Move> {
+        self.moves(game_data)
+            .into_iter()
+            .filter(|book_move| book_move.weight > 0)
+            .max_by_key(|book_move| (book_move.weight, book_move.games))
+            // Ход может оказаться недоступным при совпадении хешей разных позиций
+            .and_then(|book_move| game_data.parse_move(&book_move.notation))
+    }
+
+    /// Записывает книгу в двоичном формате
+    ///
+    /// Заголовок: "CKBK", версия, название разновидности игры (длина u16 и UTF-8)
+    /// и количество позиций (u32). Каждая позиция: хеш (u64), количество ходов (u16) и ходы:
+    /// запись хода (длина u8 и ASCII), вес, количество партий, побед белых, ничьих
+    /// и побед чёрных (u32). Все числа записываются в порядке little-endian
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let name = self.variant.to_string();
+        writer.write_all(Self::MAGIC)?;
+        writer.write_all(&[Self::VERSION])?;
+        writer.write_all(&(name.len() as u16).to_le_bytes())?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&(self.positions.len() as u32).to_le_bytes())?;
+        let mut hashes: Vec<u64> = self.positions.keys().copied().collect();
+        hashes.sort_unstable();
+        for hash in hashes {
+            let moves = &self.positions[&hash];
+            writer.write_all(&hash.to_le_bytes())?;
+            writer.write_all(&(moves.len() as u16).to_le_bytes())?;
+            for book_move in moves {
+                writer.write_all(&[book_move.notation.len() as u8])?;
+                writer.write_all(book_move.notation.as_bytes())?;
+                for number in [
+                    book_move.weight,
+                    book_move.games,
+                    book_move.white_wins,
+                    book_move.draws,
+                    book_move.black_wins,
+                ] {
+                    writer.write_all(&number.to_le_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Читает книгу, записанную [OpeningBook::write_to]
+    pub fn read_from(reader: &mut impl Read, variant: Variant) -> Result<Self, OpeningBookError> {
+        let invalid = |reason: &str| OpeningBookError::InvalidFormat(reason.to_string());
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(invalid("неизвестный формат файла"));
+        }
+        let [version] = read_bytes(reader)?;
+        if version != Self::VERSION {
+            return Err(invalid("неподдерживаемая версия формата"));
+        }
+        let name_len = u16::from_le_bytes(read_bytes(reader)?);
+        let name = String::from_utf8(read_vec(reader, name_len as usize)?)
+            .map_err(|_| invalid("название правил не в UTF-8"))?;
+        if name != variant.to_string() {
+            return Err(OpeningBookError::VariantMismatch {
+                expected: variant.to_string(),
+                found: name,
+            });
+        }
+
+        let positions_count = u32::from_le_bytes(read_bytes(reader)?);
+        let mut positions = HashMap::new();
+        for _ in 0..positions_count {
+            let hash = u64::from_le_bytes(read_bytes(reader)?);
+            let moves_count = u16::from_le_bytes(read_bytes(reader)?);
+            let mut moves = Vec::with_capacity(moves_count as usize);
+            for _ in 0..moves_count {
+                let [notation_len] = read_bytes(reader)?;
+                let notation = String::from_utf8(read_vec(reader, notation_len as usize)?)
+                    .map_err(|_| invalid("запись хода не в ASCII"))?;
+                let mut numbers = [0; 5];
+                for number in &mut numbers {
+                    *number = u32::from_le_bytes(read_bytes(reader)?);
+                }
+                let [weight, games, white_wins, draws, black_wins] = numbers;
+                moves.push(BookMove {
+                    notation,
+                    weight,
+                    games,
+                    white_wins,
+                    draws,
+                    black_wins,
+                });
+            }
+            positions.insert(hash, moves);
+        }
+        Ok(Self { variant, positions })
+    }
+}
+
+/// Читает из потока массив байтов фиксированной длины
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Читает из потока указанное количество байтов
+fn read_vec(reader: &mut impl Read, len: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
@@ -0,0 +1,74 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use serde::{Deserialize, Serialize};
+
+use crate::structs::Position;
+
+/// Ход фигуры
+///
+/// Содержит всю информацию, необходимую для того, чтобы восстановить точный путь фигуры:
+/// для записи партии, анимации и воспроизведения
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Move {
+    /// Начальная позиция фигуры
+    pub from: Position,
+    /// Все ячейки, на которые фигура вставала во время хода, последняя из них - конечная позиция
+    ///
+    /// При обычном перемещении содержит одну ячейку
+    pub path: Vec<Position>,
+    /// Позиции взятых фигур противника в порядке их взятия
+    pub taken_pieces_positions: Vec<Position>,
+    /// Превращается ли шашка в дамку в результате хода
+    pub promotion: bool,
+}
+
+impl Move {
+    /// Создаёт обычное перемещение фигуры (без взятия)
+    pub fn movement(from: Position, to: Position, promotion: bool) -> Self {
+        Self {
+            from,
+            path: vec![to],
+            taken_pieces_positions: Vec::new(),
+            promotion,
+        }
+    }
+
+    /// Создаёт взятие фигур противника
+    pub fn taking(
+        from: Position,
+        path: Vec<Position>,
+        taken_pieces_positions: Vec<Position>,
+        promotion: bool,
+    ) -> Self {
+        Self {
+            from,
+            path,
+            taken_pieces_positions,
+            promotion,
+        }
+    }
+
+    /// Возвращает конечную позицию фигуры
+    pub fn to(&self) -> Position {
+        *self.path.last().expect("путь хода не может быть пустым")
+    }
+
+    /// Является ли ход взятием фигур противника
+    pub fn is_taking(&self) -> bool {
+        !self.taken_pieces_positions.is_empty()
+    }
+}
@@ -73,14 +73,57 @@ impl Position {
         neighbours
     }
 
+    /// Возвращает позиции клеток-соседей, которые находятся на вертикали и горизонтали, содержащих
+    /// ячейку на данной позиции
+    ///
+    /// Параметры:
+    /// step: на каком расстоянии находятся клетки-соседи
+    pub fn orthogonal_neighbours(&self, steps: i8) -> Vec<(Position, Direction)> {
+        let mut neighbours = Vec::with_capacity(steps as usize * 4);
+        for offset in 1..=steps {
+            neighbours.extend([
+                (
+                    Position::from((self.row - offset, self.column)),
+                    Direction::Up,
+                ),
+                (
+                    Position::from((self.row + offset, self.column)),
+                    Direction::Down,
+                ),
+                (
+                    Position::from((self.row, self.column - offset)),
+                    Direction::Left,
+                ),
+                (
+                    Position::from((self.row, self.column + offset)),
+                    Direction::Right,
+                ),
+            ]);
+        }
+        neighbours
+    }
+
+    /// Возвращает позиции клеток-соседей по диагоналям, вертикали и горизонтали, содержащим
+    /// ячейку на данной позиции
+    ///
+    /// Параметры:
+    /// step: на каком расстоянии находятся клетки-соседи
+    pub fn neighbours(&self, steps: i8) -> Vec<(Position, Direction)> {
+        let mut neighbours = self.diagonal_neighbours(steps);
+        neighbours.extend(self.orthogonal_neighbours(steps));
+        neighbours
+    }
+
+    /// Возвращает ячейку, которая находится рядом с текущей в указанном направлении
+    pub fn next(&self, direction: Direction) -> Position {
+        let (row_offset, column_offset) = direction.offset();
+        (self.row + row_offset, self.column + column_offset).into()
+    }
+
     // Возвращает ячейку, которая находится рядом с текущей по диагонали, в указанном направлении
     pub fn next_diagonal(&self, direction: Direction) -> Position {
-        match direction {
-            Direction::LeftUp => (self.row - 1, self.column - 1).into(),
-            Direction::LeftDown => (self.row + 1, self.column - 1).into(),
-            Direction::RightDown => (self.row + 1, self.column + 1).into(),
-            Direction::RightUp => (self.row - 1, self.column + 1).into(),
-        }
+        debug_assert!(Direction::DIAGONAL.contains(&direction));
+        self.next(direction)
     }
 }
 
@@ -0,0 +1,228 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::{collections::HashSet, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::{CustomVariantError, Direction, KingRange, Promotion, Side, Variant},
+    traits::Ruleset,
+};
+
+/// Правила, описанные пользователем в TOML файле
+///
+/// Не указанные параметры берутся из правил русских шашек. При чтении (в том числе из
+/// сохранённой партии) правила проверяются, см. [CustomRuleset::validate]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "CustomRulesetFields")]
+pub struct CustomRuleset {
+    /// Название разновидности игры, отображаемое в списке правил
+    pub name: String,
+    /// Количество горизонталей и вертикалей доски
+    pub board_size: i8,
+    /// Количество рядов шашек у каждой стороны в начальной позиции
+    pub initial_rows: i8,
+    /// Могут ли шашки бить назад
+    pub men_capture_backwards: bool,
+    /// Являются ли дамки "дальнобойными"
+    pub flying_kings: bool,
+    /// Обязательно ли взятие
+    pub mandatory_capture: bool,
+    /// Действует ли правило большинства
+    pub majority_capture: bool,
+    /// Продолжает ли шашка взятие как дамка, если достигла последней горизонтали во время взятия
+    pub promotion_continues_move: bool,
+}
+
+impl Default for CustomRuleset {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            board_size: 8,
+            initial_rows: 3,
+            men_capture_backwards: true,
+            flying_kings: true,
+            mandatory_capture: true,
+            majority_capture: false,
+            promotion_continues_move: true,
+        }
+    }
+}
+
+/// Параметры правил в том виде, в котором они записаны, до проверки
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CustomRulesetFields {
+    name: String,
+    board_size: i8,
+    initial_rows: i8,
+    men_capture_backwards: bool,
+    flying_kings: bool,
+    mandatory_capture: bool,
+    majority_capture: bool,
+    promotion_continues_move: bool,
+}
+
+impl Default for CustomRulesetFields {
+    fn default() -> Self {
+        let ruleset = CustomRuleset::default();
+        Self {
+            name: ruleset.name,
+            board_size: ruleset.board_size,
+            initial_rows: ruleset.initial_rows,
+            men_capture_backwards: ruleset.men_capture_backwards,
+            flying_kings: ruleset.flying_kings,
+            mandatory_capture: ruleset.mandatory_capture,
+            majority_capture: ruleset.majority_capture,
+            promotion_continues_move: ruleset.promotion_continues_move,
+        }
+    }
+}
+
+impl TryFrom<CustomRulesetFields> for CustomRuleset {
+    type Error = CustomVariantError;
+
+    fn try_from(fields: CustomRulesetFields) -> Result<Self, Self::Error> {
+        let ruleset = Self {
+            name: fields.name.trim().to_string(),
+            board_size: fields.board_size,
+            initial_rows: fields.initial_rows,
+            men_capture_backwards: fields.men_capture_backwards,
+            flying_kings: fields.flying_kings,
+            mandatory_capture: fields.mandatory_capture,
+            majority_capture: fields.majority_capture,
+            promotion_continues_move: fields.promotion_continues_move,
+        };
+        ruleset.validate()?;
+        Ok(ruleset)
+    }
+}
+
+/// Содержимое файла с пользовательскими правилами: список таблиц [[variant]]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomVariantsFile {
+    #[serde(default)]
+    variant: Vec<CustomRulesetFields>,
+}
+
+impl CustomRuleset {
+    /// Наименьший размер доски
+    pub const MIN_BOARD_SIZE: i8 = 4;
+    /// Наибольший размер доски
+    pub const MAX_BOARD_SIZE: i8 = 16;
+
+    /// Наибольшее количество рядов шашек, при котором между сторонами остаётся свободная горизонталь
+    pub fn max_initial_rows(board_size: i8) -> i8 {
+        (board_size - 1) / 2
+    }
+
+    /// Проверяет, что по описанным правилам можно играть
+    pub fn validate(&self) -> Result<(), CustomVariantError> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err(CustomVariantError::EmptyName);
+        }
+        if !(Self::MIN_BOARD_SIZE..=Self::MAX_BOARD_SIZE).contains(&self.board_size) {
+            return Err(CustomVariantError::InvalidBoardSize {
+                name: name.to_string(),
+                board_size: self.board_size,
+            });
+        }
+        if !(1..=Self::max_initial_rows(self.board_size)).contains(&self.initial_rows) {
+            return Err(CustomVariantError::InvalidInitialRows {
+                name: name.to_string(),
+                initial_rows: self.initial_rows,
+                board_size: self.board_size,
+            });
+        }
+        if self.majority_capture && !self.mandatory_capture {
+            return Err(CustomVariantError::MajorityRuleWithoutMandatoryTaking {
+                name: name.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Разбирает и проверяет описания правил в формате TOML
+    ///
+    /// Названия правил не должны совпадать между собой и с названиями встроенных разновидностей игры
+    pub fn parse_variants(content: &str) -> Result<Vec<CustomRuleset>, CustomVariantError> {
+        // Таблицы проверяются по одной, чтобы ошибка в правилах не сводилась к ошибке разбора TOML
+        let file: CustomVariantsFile = toml::from_str(content)?;
+        let mut names: HashSet<String> = Variant::ALL.iter().map(Variant::to_string).collect();
+        let mut rulesets = Vec::with_capacity(file.variant.len());
+        for fields in file.variant {
+            let ruleset = CustomRuleset::try_from(fields)?;
+            if !names.insert(ruleset.name.clone()) {
+                return Err(CustomVariantError::DuplicateName(ruleset.name));
+            }
+            rulesets.push(ruleset);
+        }
+        Ok(rulesets)
+    }
+
+    /// Загружает и проверяет описания правил из TOML файла
+    pub fn load_variants(path: impl AsRef<Path>) -> Result<Vec<CustomRuleset>, CustomVariantError> {
+        Self::parse_variants(&fs::read_to_string(path)?)
+    }
+}
+
+impl Ruleset for CustomRuleset {
+    fn board_size(&self) -> (i8, i8) {
+        (self.board_size, self.board_size)
+    }
+
+    fn initial_rows(&self) -> i8 {
+        self.initial_rows
+    }
+
+    fn man_movement_directions(&self, side: Side) -> Vec<Direction> {
+        Vec::from(Direction::forward_diagonals(side))
+    }
+
+    fn man_taking_directions(&self, side: Side) -> Vec<Direction> {
+        if self.men_capture_backwards {
+            Vec::from(Direction::DIAGONAL)
+        } else {
+            Vec::from(Direction::forward_diagonals(side))
+        }
+    }
+
+    fn king_range(&self) -> KingRange {
+        if self.flying_kings {
+            KingRange::Flying
+        } else {
+            KingRange::Short
+        }
+    }
+
+    fn is_taking_mandatory(&self) -> bool {
+        self.mandatory_capture
+    }
+
+    fn is_majority_rule(&self) -> bool {
+        self.majority_capture
+    }
+
+    fn promotion(&self) -> Promotion {
+        if self.promotion_continues_move {
+            Promotion::ContinuesMove
+        } else {
+            Promotion::AtEndOfMove
+        }
+    }
+}
@@ -0,0 +1,95 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{Direction, KingRange, Piece, Promotion, Side},
+    structs::{rulesets::select_best_takings, GameData, Move},
+    traits::Ruleset,
+};
+
+/// Правила фризских шашек
+///
+/// Доска 10x10, по 20 шашек у каждой стороны. Фигуры ходят по диагонали, но бьют также по
+/// вертикали и горизонтали. Из возможных взятий выбирается взятие наибольшей "ценности",
+/// дамка ценится чуть меньше двух шашек. Если у стороны есть и дамки, и шашки, то перемещать
+/// дамки (без взятия) можно не более трёх раз подряд
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FrisianRuleset;
+
+impl FrisianRuleset {
+    /// Ценность взятой шашки
+    const MAN_VALUE: u32 = 100;
+    /// Ценность взятой дамки: немного меньше ценности двух шашек
+    const KING_VALUE: u32 = 2 * Self::MAN_VALUE - 1;
+}
+
+impl Ruleset for FrisianRuleset {
+    fn board_size(&self) -> (i8, i8) {
+        (10, 10)
+    }
+
+    fn initial_rows(&self) -> i8 {
+        4
+    }
+
+    fn man_movement_directions(&self, side: Side) -> Vec<Direction> {
+        Vec::from(Direction::forward_diagonals(side))
+    }
+
+    fn man_taking_directions(&self, _side: Side) -> Vec<Direction> {
+        Vec::from(Direction::ALL)
+    }
+
+    fn king_range(&self) -> KingRange {
+        KingRange::Flying
+    }
+
+    fn is_taking_mandatory(&self) -> bool {
+        true
+    }
+
+    fn is_majority_rule(&self) -> bool {
+        true
+    }
+
+    fn promotion(&self) -> Promotion {
+        Promotion::AtEndOfMove
+    }
+
+    fn king_taking_directions(&self) -> Vec<Direction> {
+        Vec::from(Direction::ALL)
+    }
+
+    fn max_consecutive_king_moves(&self) -> Option<u32> {
+        Some(3)
+    }
+
+    /// Выбирается взятие наибольшей ценности, а при равенстве - взятие дамкой
+    fn select_takings(&self, game_data: &GameData, side: Side, takings: Vec<Move>) -> Vec<Move> {
+        let pieces = game_data.pieces(side);
+        let enemy_pieces = game_data.pieces(side.opposite());
+        select_best_takings(takings, |taking: &Move| {
+            let value: u32 = taking
+                .taken_pieces_positions
+                .iter()
+                .map(|position| match enemy_pieces.get(position) {
+                    Some(Piece::King) => Self::KING_VALUE,
+                    _ => Self::MAN_VALUE,
+                })
+                .sum();
+            (value, pieces.get(&taking.from) == Some(&Piece::King))
+        })
+    }
+}
@@ -0,0 +1,97 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{Direction, KingRange, Piece, Promotion, Side},
+    structs::{GameData, Move, Position},
+    traits::Ruleset,
+};
+
+/// Игра "в поддавки" по правилам другой разновидности игры
+///
+/// Ходы совершаются по исходным правилам, но выигрывает сторона, которая первой лишилась всех
+/// фигур или возможности сделать ход
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GiveawayRuleset<R: Ruleset>(pub R);
+
+impl<R: Ruleset> Ruleset for GiveawayRuleset<R> {
+    fn board_size(&self) -> (i8, i8) {
+        self.0.board_size()
+    }
+
+    fn initial_rows(&self) -> i8 {
+        self.0.initial_rows()
+    }
+
+    fn man_movement_directions(&self, side: Side) -> Vec<Direction> {
+        self.0.man_movement_directions(side)
+    }
+
+    fn man_taking_directions(&self, side: Side) -> Vec<Direction> {
+        self.0.man_taking_directions(side)
+    }
+
+    fn king_range(&self) -> KingRange {
+        self.0.king_range()
+    }
+
+    fn is_taking_mandatory(&self) -> bool {
+        self.0.is_taking_mandatory()
+    }
+
+    fn is_majority_rule(&self) -> bool {
+        self.0.is_majority_rule()
+    }
+
+    fn promotion(&self) -> Promotion {
+        self.0.promotion()
+    }
+
+    fn first_move(&self) -> Side {
+        self.0.first_move()
+    }
+
+    fn king_directions(&self) -> Vec<Direction> {
+        self.0.king_directions()
+    }
+
+    fn king_taking_directions(&self) -> Vec<Direction> {
+        self.0.king_taking_directions()
+    }
+
+    fn max_consecutive_king_moves(&self) -> Option<u32> {
+        self.0.max_consecutive_king_moves()
+    }
+
+    fn is_giveaway(&self) -> bool {
+        true
+    }
+
+    fn can_take(&self, piece: Piece, enemy_piece: Piece) -> bool {
+        self.0.can_take(piece, enemy_piece)
+    }
+
+    fn is_playable_cell(&self, position: Position) -> bool {
+        self.0.is_playable_cell(position)
+    }
+
+    fn initial_positions(&self, side: Side) -> Vec<Position> {
+        self.0.initial_positions(side)
+    }
+
+    fn select_takings(&self, game_data: &GameData, side: Side, takings: Vec<Move>) -> Vec<Move> {
+        self.0.select_takings(game_data, side, takings)
+    }
+}
@@ -0,0 +1,61 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{Direction, KingRange, Promotion, Side},
+    traits::Ruleset,
+};
+
+/// Правила международных шашек
+///
+/// Доска 10x10, по 20 шашек у каждой стороны, шашки бьют назад, дамки "дальнобойные",
+/// действует правило большинства. Шашка становится дамкой, только если закончила ход
+/// на последней горизонтали
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InternationalRuleset;
+
+impl Ruleset for InternationalRuleset {
+    fn board_size(&self) -> (i8, i8) {
+        (10, 10)
+    }
+
+    fn initial_rows(&self) -> i8 {
+        4
+    }
+
+    fn man_movement_directions(&self, side: Side) -> Vec<Direction> {
+        Vec::from(Direction::forward_diagonals(side))
+    }
+
+    fn man_taking_directions(&self, _side: Side) -> Vec<Direction> {
+        Vec::from(Direction::DIAGONAL)
+    }
+
+    fn king_range(&self) -> KingRange {
+        KingRange::Flying
+    }
+
+    fn is_taking_mandatory(&self) -> bool {
+        true
+    }
+
+    fn is_majority_rule(&self) -> bool {
+        true
+    }
+
+    fn promotion(&self) -> Promotion {
+        Promotion::AtEndOfMove
+    }
+}
@@ -0,0 +1,90 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{Direction, KingRange, Piece, Promotion, Side},
+    structs::{rulesets::select_best_takings, GameData, Move},
+    traits::Ruleset,
+};
+
+/// Правила итальянских шашек
+///
+/// Доска 8x8, по 12 шашек у каждой стороны, шашки ходят и бьют только вперёд и не могут
+/// брать дамок, дамки ходят на одну клетку. Из возможных взятий выбирается лучшее по
+/// количеству и "качеству" взятых фигур
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ItalianRuleset;
+
+impl Ruleset for ItalianRuleset {
+    fn board_size(&self) -> (i8, i8) {
+        (8, 8)
+    }
+
+    fn initial_rows(&self) -> i8 {
+        3
+    }
+
+    fn man_movement_directions(&self, side: Side) -> Vec<Direction> {
+        Vec::from(Direction::forward_diagonals(side))
+    }
+
+    fn man_taking_directions(&self, side: Side) -> Vec<Direction> {
+        Vec::from(Direction::forward_diagonals(side))
+    }
+
+    fn king_range(&self) -> KingRange {
+        KingRange::Short
+    }
+
+    fn is_taking_mandatory(&self) -> bool {
+        true
+    }
+
+    fn is_majority_rule(&self) -> bool {
+        true
+    }
+
+    fn promotion(&self) -> Promotion {
+        Promotion::AtEndOfMove
+    }
+
+    fn can_take(&self, piece: Piece, enemy_piece: Piece) -> bool {
+        // Шашка не может взять дамку
+        piece.is_king() || enemy_piece.is_man()
+    }
+
+    /// Правила выбора взятия, по убыванию приоритета:
+    /// 1. взять наибольшее количество фигур
+    /// 2. бить дамкой, а не шашкой
+    /// 3. взять наибольшее количество дамок
+    /// 4. взять дамку как можно раньше
+    fn select_takings(&self, game_data: &GameData, side: Side, takings: Vec<Move>) -> Vec<Move> {
+        let pieces = game_data.pieces(side);
+        let enemy_pieces = game_data.pieces(side.opposite());
+        select_best_takings(takings, |taking: &Move| {
+            let taken_kings: Vec<bool> = taking
+                .taken_pieces_positions
+                .iter()
+                .map(|position| enemy_pieces.get(position) == Some(&Piece::King))
+                .collect();
+            (
+                taking.taken_pieces_positions.len(),
+                pieces.get(&taking.from) == Some(&Piece::King),
+                taken_kings.iter().filter(|is_king| **is_king).count(),
+                taken_kings,
+            )
+        })
+    }
+}
@@ -0,0 +1,36 @@
+mod brazilian;
+mod canadian;
+mod custom;
+mod english;
+mod frisian;
+mod giveaway;
+mod international;
+mod italian;
+mod russian;
+mod spanish;
+mod turkish;
+
+pub use self::{
+    brazilian::BrazilianRuleset, canadian::CanadianRuleset, custom::CustomRuleset,
+    english::EnglishRuleset, frisian::FrisianRuleset, giveaway::GiveawayRuleset,
+    international::InternationalRuleset, italian::ItalianRuleset, russian::RussianRuleset,
+    spanish::SpanishRuleset, turkish::TurkishRuleset,
+};
+
+use crate::structs::Move;
+
+/// Оставляет только взятия с наибольшим значением ключа
+///
+/// Используется правилами, в которых выбор взятия зависит не только от количества взятых фигур
+pub(crate) fn select_best_takings<K: Ord>(
+    takings: Vec<Move>,
+    key: impl Fn(&Move) -> K,
+) -> Vec<Move> {
+    let Some(best_key) = takings.iter().map(&key).max() else {
+        return takings;
+    };
+    takings
+        .into_iter()
+        .filter(|taking| key(taking) == best_key)
+        .collect()
+}
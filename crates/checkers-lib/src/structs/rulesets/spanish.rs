@@ -0,0 +1,79 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{Direction, KingRange, Piece, Promotion, Side},
+    structs::{rulesets::select_best_takings, GameData, Move},
+    traits::Ruleset,
+};
+
+/// Правила испанских шашек
+///
+/// Доска 8x8, по 12 шашек у каждой стороны, шашки ходят и бьют только вперёд, дамки
+/// "дальнобойные". Нужно взять наибольшее количество фигур, а при равенстве - наибольшее
+/// количество дамок.
+///
+/// Португальские шашки отличаются от испанских только расположением доски, поэтому
+/// используют эти же правила
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpanishRuleset;
+
+impl Ruleset for SpanishRuleset {
+    fn board_size(&self) -> (i8, i8) {
+        (8, 8)
+    }
+
+    fn initial_rows(&self) -> i8 {
+        3
+    }
+
+    fn man_movement_directions(&self, side: Side) -> Vec<Direction> {
+        Vec::from(Direction::forward_diagonals(side))
+    }
+
+    fn man_taking_directions(&self, side: Side) -> Vec<Direction> {
+        Vec::from(Direction::forward_diagonals(side))
+    }
+
+    fn king_range(&self) -> KingRange {
+        KingRange::Flying
+    }
+
+    fn is_taking_mandatory(&self) -> bool {
+        true
+    }
+
+    fn is_majority_rule(&self) -> bool {
+        true
+    }
+
+    fn promotion(&self) -> Promotion {
+        Promotion::AtEndOfMove
+    }
+
+    fn select_takings(&self, game_data: &GameData, side: Side, takings: Vec<Move>) -> Vec<Move> {
+        let enemy_pieces = game_data.pieces(side.opposite());
+        select_best_takings(takings, |taking: &Move| {
+            (
+                taking.taken_pieces_positions.len(),
+                taking
+                    .taken_pieces_positions
+                    .iter()
+                    .filter(|position| enemy_pieces.get(*position) == Some(&Piece::King))
+                    .count(),
+            )
+        })
+    }
+}
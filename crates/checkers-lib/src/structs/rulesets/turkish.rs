@@ -0,0 +1,89 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{Direction, KingRange, Promotion, Side},
+    structs::Position,
+    traits::Ruleset,
+};
+
+/// Правила турецких шашек
+///
+/// Доска 8x8, используются все клетки. У каждой стороны по 16 шашек, стоящих на второй и третьей
+/// горизонталях. Шашки ходят и бьют вперёд и в стороны, дамки "дальнобойные" и ходят по
+/// вертикалям и горизонталям. Действует правило большинства, взятые фигуры снимаются с доски
+/// после завершения хода
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TurkishRuleset;
+
+impl Ruleset for TurkishRuleset {
+    fn board_size(&self) -> (i8, i8) {
+        (8, 8)
+    }
+
+    fn initial_rows(&self) -> i8 {
+        2
+    }
+
+    fn man_movement_directions(&self, side: Side) -> Vec<Direction> {
+        vec![
+            Direction::forward_orthogonal(side),
+            Direction::Left,
+            Direction::Right,
+        ]
+    }
+
+    fn man_taking_directions(&self, side: Side) -> Vec<Direction> {
+        self.man_movement_directions(side)
+    }
+
+    fn king_range(&self) -> KingRange {
+        KingRange::Flying
+    }
+
+    fn is_taking_mandatory(&self) -> bool {
+        true
+    }
+
+    fn is_majority_rule(&self) -> bool {
+        true
+    }
+
+    fn promotion(&self) -> Promotion {
+        Promotion::AtEndOfMove
+    }
+
+    fn king_directions(&self) -> Vec<Direction> {
+        Vec::from(Direction::ORTHOGONAL)
+    }
+
+    fn is_playable_cell(&self, _position: Position) -> bool {
+        true
+    }
+
+    /// Крайние горизонтали изначально пусты, шашки занимают вторую и третью горизонтали
+    fn initial_positions(&self, side: Side) -> Vec<Position> {
+        use itertools::Itertools;
+        let (rows, columns) = self.board_size();
+        let side_rows = match side {
+            Side::Black => 1..1 + self.initial_rows(),
+            Side::White => rows - 1 - self.initial_rows()..rows - 1,
+        };
+        side_rows
+            .cartesian_product(0..columns)
+            .map(Position::from)
+            .collect()
+    }
+}
@@ -0,0 +1,555 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{
+    enums::{Piece, Side, TablebaseError, TablebaseValue, Variant},
+    structs::{GameData, Material, Move, Position},
+};
+
+/// Таблицы эндшпиля: точные результаты всех позиций с небольшим количеством фигур
+///
+/// Таблицы строятся ретроградным анализом отдельно для каждого состава фигур (см. [Material]),
+/// начиная с составов, в которые переходят остальные при взятиях и превращениях в дамки.
+/// Правила ничьей по количеству ходов не учитываются
+pub struct Tablebase {
+    variant: Variant,
+    max_pieces: u32,
+    indexer: Indexer,
+    /// Коды результатов позиций (см. [TablebaseValue]) по составу фигур и индексу позиции
+    tables: HashMap<Material, Vec<u16>>,
+}
+
+/// Позиция, результат которой ещё не определён при построении таблицы
+struct PendingPosition {
+    /// Индекс позиции в таблице
+    index: u32,
+    /// Начало и количество ходов, не меняющих состав фигур, в общем списке ходов
+    successors_start: u32,
+    successors_len: u16,
+    /// Лучший для стороны, которая ходит, результат ходов, меняющих состав фигур
+    external: Option<TablebaseValue>,
+}
+
+impl Tablebase {
+    /// Наибольшее количество фигур, для которого строятся таблицы
+    pub const MAX_PIECES: u32 = 6;
+    /// Первые байты файла с таблицами
+    const MAGIC: &'static [u8; 4] = b"CKTB";
+    /// Версия формата файла
+    const VERSION: u8 = 1;
+    /// Код ещё не определённого результата при построении таблицы
+    const UNRESOLVED: u16 = u16::MAX;
+
+    /// Строит таблицы для позиций, в которых от 1 до max_pieces фигур
+    ///
+    /// Перед построением таблицы каждого состава вызывается on_material, например, чтобы показать
+    /// прогресс. Построение шестифигурных таблиц занимает много времени и памяти
+    pub fn generate(
+        variant: Variant,
+        max_pieces: u32,
+        mut on_material: impl FnMut(&Material),
+    ) -> Result<Self, TablebaseError> {
+        Self::check_variant(&variant)?;
+        if max_pieces > Self::MAX_PIECES {
+            return Err(TablebaseError::TooManyPieces(max_pieces));
+        }
+        let indexer = Indexer::new(&variant);
+        let mut tablebase = Self {
+            variant,
+            max_pieces,
+            indexer,
+            tables: HashMap::new(),
+        };
+        for material in Material::up_to(max_pieces) {
+            if u32::try_from(tablebase.indexer.table_size(&material)).is_err() {
+                return Err(TablebaseError::TooManyPieces(max_pieces));
+            }
+            on_material(&material);
+            let table = tablebase.generate_table(material);
+            tablebase.tables.insert(material, table);
+        }
+        Ok(tablebase)
+    }
+
+    /// Загружает таблицы, построенные для указанной разновидности игры
+    pub fn load(path: impl AsRef<Path>, variant: Variant) -> Result<Self, TablebaseError> {
+        Self::read_from(&mut BufReader::new(File::open(path)?), variant)
+    }
+
+    /// Сохраняет таблицы в файл
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), TablebaseError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Имя файла с таблицами указанной разновидности игры
+    pub fn default_file_name(variant: &Variant) -> String {
+        variant.file_name("cktb")
+    }
+
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    pub fn max_pieces(&self) -> u32 {
+        self.max_pieces
+    }
+
+    /// Возвращает результат позиции или None, если позиции нет в таблицах
+    pub fn probe(&self, game_data: &GameData) -> Option<TablebaseValue> {
+        if game_data.variant != self.variant || game_data.extra_moves > 0 {
+            return None;
+        }
+        let material = Material::of(game_data);
+        if material.pieces() > self.max_pieces {
+            return None;
+        }
+        let table = self.tables.get(&material)?;
+        let index = self.indexer.index(game_data, &material)?;
+        Some(TablebaseValue::from_code(table[index]))
+    }
+
+    /// Возвращает лучший ход по таблицам и результат позиции после него для стороны,
+    /// которая ходит. None - если ходов нет или позиции нет в таблицах
+    pub fn best_move(&self, game_data: &GameData) -> Option<(Move, TablebaseValue)> {
+        let mut best: Option<(Move, TablebaseValue)> = None;
+        for piece_move in game_data.get_all_available_moves() {
+            let mut child = game_data.clone();
+            child.make_move(game_data.current_move, &piece_move);
+            let value = self.probe(&child)?.for_previous_move();
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_value)| value.preference() > best_value.preference())
+            {
+                best = Some((piece_move, value));
+            }
+        }
+        best
+    }
+
+    /// Записывает таблицы в компактном двоичном формате
+    ///
+    /// Заголовок: "CKTB", версия, название разновидности игры (длина u16 и UTF-8),
+    /// наибольшее количество фигур и количество таблиц (u32). Каждая таблица: состав фигур
+    /// (4 байта), размер кода результата (1 или 2 байта), количество позиций (u64) и коды
+    /// результатов позиций. Все числа записываются в порядке little-endian
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let name = self.variant.to_string();
+        writer.write_all(Self::MAGIC)?;
+        writer.write_all(&[Self::VERSION])?;
+        writer.write_all(&(name.len() as u16).to_le_bytes())?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&[self.max_pieces as u8])?;
+        writer.write_all(&(self.tables.len() as u32).to_le_bytes())?;
+        for material in Material::up_to(self.max_pieces) {
+            let Some(table) = self.tables.get(&material) else {
+                continue;
+            };
+            let width: u8 = if table.iter().all(|code| *code <= u8::MAX as u16) {
+                1
+            } else {
+                2
+            };
+            writer.write_all(&[
+                material.white_men,
+                material.white_kings,
+                material.black_men,
+                material.black_kings,
+                width,
+            ])?;
+            writer.write_all(&(table.len() as u64).to_le_bytes())?;
+            let bytes: Vec<u8> = if width == 1 {
+                table.iter().map(|code| *code as u8).collect()
+            } else {
+                table.iter().flat_map(|code| code.to_le_bytes()).collect()
+            };
+            writer.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Читает таблицы, записанные [Tablebase::write_to]
+    pub fn read_from(reader: &mut impl Read, variant: Variant) -> Result<Self, TablebaseError> {
+        Self::check_variant(&variant)?;
+        let invalid = |reason: &str| TablebaseError::InvalidFormat(reason.to_string());
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(invalid("неизвестный формат файла"));
+        }
+        let [version] = read_bytes(reader)?;
+        if version != Self::VERSION {
+            return Err(invalid("неподдерживаемая версия формата"));
+        }
+        let name_len = u16::from_le_bytes(read_bytes(reader)?);
+        let mut name = vec![0; name_len as usize];
+        reader.read_exact(&mut name)?;
+        let name = String::from_utf8(name).map_err(|_| invalid("название правил не в UTF-8"))?;
+        if name != variant.to_string() {
+            return Err(TablebaseError::VariantMismatch {
+                expected: variant.to_string(),
+                found: name,
+            });
+        }
+        let [max_pieces] = read_bytes(reader)?;
+        let max_pieces = max_pieces as u32;
+        if max_pieces > Self::MAX_PIECES {
+            return Err(TablebaseError::TooManyPieces(max_pieces));
+        }
+        let tables_count = u32::from_le_bytes(read_bytes(reader)?);
+
+        let indexer = Indexer::new(&variant);
+        let mut tables = HashMap::new();
+        for _ in 0..tables_count {
+            let [white_men, white_kings, black_men, black_kings, width] = read_bytes(reader)?;
+            let material = Material {
+                white_men,
+                white_kings,
+                black_men,
+                black_kings,
+            };
+            let len = u64::from_le_bytes(read_bytes(reader)?) as usize;
+            if material.pieces() > max_pieces || len != indexer.table_size(&material) {
+                return Err(invalid("размер таблицы не соответствует правилам"));
+            }
+            let table = match width {
+                1 => {
+                    let mut bytes = vec![0; len];
+                    reader.read_exact(&mut bytes)?;
+                    bytes.into_iter().map(u16::from).collect()
+                }
+                2 => {
+                    let mut bytes = vec![0; len * 2];
+                    reader.read_exact(&mut bytes)?;
+                    bytes
+                        .chunks_exact(2)
+                        .map(|code| u16::from_le_bytes([code[0], code[1]]))
+                        .collect()
+                }
+                _ => return Err(invalid("неизвестный размер кода результата")),
+            };
+            tables.insert(material, table);
+        }
+        Ok(Self {
+            variant,
+            max_pieces,
+            indexer,
+            tables,
+        })
+    }
+
+    /// Таблицы строятся только для правил, в которых ходы зависят лишь от расстановки фигур
+    fn check_variant(variant: &Variant) -> Result<(), TablebaseError> {
+        if variant.ruleset().max_consecutive_king_moves().is_some() {
+            Err(TablebaseError::UnsupportedVariant(variant.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Строит таблицу для одного состава фигур
+    ///
+    /// Сначала для каждой позиции находятся ходы: ходы, меняющие состав фигур, сразу оцениваются
+    /// по уже построенным таблицам, остальные запоминаются. Затем на проходе номер n определяются
+    /// позиции, которые выигрываются или проигрываются ровно за n полуходов. Позиции, оставшиеся
+    /// неопределёнными, когда очередной проход ничего не изменил, - ничейные
+    fn generate_table(&self, material: Material) -> Vec<u16> {
+        let size = self.indexer.table_size(&material);
+        let mut values = vec![Self::UNRESOLVED; size];
+        let mut successors: Vec<u32> = Vec::new();
+        let mut pending = Vec::new();
+        // Результаты ходов, меняющих состав фигур, учитываются на проходе, номер которого
+        // равен расстоянию до конца партии
+        let mut max_external_plies = 0;
+        for (index, value) in values.iter_mut().enumerate() {
+            let Some(game_data) = self.indexer.game_data(&material, index) else {
+                continue;
+            };
+            let moves = game_data.get_all_available_moves();
+            if moves.is_empty() {
+                let terminal = if game_data.ruleset().is_giveaway() {
+                    TablebaseValue::Win(0)
+                } else {
+                    TablebaseValue::Loss(0)
+                };
+                *value = terminal.code();
+                continue;
+            }
+            let successors_start = successors.len() as u32;
+            let mut external: Option<TablebaseValue> = None;
+            for piece_move in &moves {
+                let mut child = game_data.clone();
+                child.make_move(game_data.current_move, piece_move);
+                let child_material = Material::of(&child);
+                if child_material == material {
+                    let child_index = self
+                        .indexer
+                        .index(&child, &child_material)
+                        .expect("позиция после хода должна быть в таблице");
+                    successors.push(child_index as u32);
+                } else {
+                    let value = self
+                        .probe(&child)
+                        .expect("составы после взятия или превращения строятся раньше")
+                        .for_previous_move();
+                    if external.is_none_or(|external| value.preference() > external.preference()) {
+                        external = Some(value);
+                    }
+                }
+            }
+            if let Some(TablebaseValue::Win(plies) | TablebaseValue::Loss(plies)) = external {
+                max_external_plies = max_external_plies.max(plies);
+            }
+            pending.push(PendingPosition {
+                index: index as u32,
+                successors_start,
+                successors_len: (successors.len() as u32 - successors_start) as u16,
+                external,
+            });
+        }
+
+        let mut plies = 0;
+        loop {
+            plies += 1;
+            let mut resolved = Vec::new();
+            pending.retain(
+                |position| match Self::resolve(position, &values, &successors, plies) {
+                    Some(value) => {
+                        resolved.push((position.index as usize, value.code()));
+                        false
+                    }
+                    None => true,
+                },
+            );
+            if resolved.is_empty() && plies > max_external_plies {
+                break;
+            }
+            for (index, code) in resolved {
+                values[index] = code;
+            }
+        }
+        // Неопределённые позиции ничейные, а индексы невозможных расстановок не используются
+        for value in &mut values {
+            if *value == Self::UNRESOLVED {
+                *value = TablebaseValue::Draw.code();
+            }
+        }
+        values
+    }
+
+    /// Определяет результат позиции на проходе номер plies, если он уже известен
+    fn resolve(
+        position: &PendingPosition,
+        values: &[u16],
+        successors: &[u32],
+        plies: u32,
+    ) -> Option<TablebaseValue> {
+        let mut fastest_win: Option<u32> = None;
+        let mut longest_loss = 0;
+        let mut all_lost = true;
+        let mut consider = |value: TablebaseValue| match value {
+            TablebaseValue::Win(plies) => {
+                fastest_win = Some(fastest_win.map_or(plies, |fastest| fastest.min(plies)))
+            }
+            TablebaseValue::Loss(plies) => longest_loss = longest_loss.max(plies),
+            TablebaseValue::Draw => all_lost = false,
+        };
+        match position.external {
+            Some(TablebaseValue::Win(external_plies) | TablebaseValue::Loss(external_plies))
+                if external_plies > plies =>
+            {
+                consider(TablebaseValue::Draw)
+            }
+            Some(value) => consider(value),
+            None => {}
+        }
+        let start = position.successors_start as usize;
+        for child in &successors[start..start + position.successors_len as usize] {
+            match values[*child as usize] {
+                Self::UNRESOLVED => consider(TablebaseValue::Draw),
+                code => consider(TablebaseValue::from_code(code).for_previous_move()),
+            }
+        }
+        match fastest_win {
+            Some(plies) => Some(TablebaseValue::Win(plies)),
+            None if all_lost => Some(TablebaseValue::Loss(longest_loss)),
+            None => None,
+        }
+    }
+}
+
+/// Нумерация позиций одного состава фигур
+///
+/// Фигуры одного вида одной стороны занимают сочетание ячеек, которое нумеруется
+/// в комбинаторной системе счисления. Индекс позиции составляется из номеров сочетаний
+/// четырёх видов фигур и стороны, которая ходит. Индексы, при которых фигуры разных видов
+/// занимают одну ячейку, не соответствуют позициям
+struct Indexer {
+    /// Позиция без фигур, из которой строятся позиции таблиц
+    empty: GameData,
+    /// Ячейки, которые могут занимать фигуры каждого вида, в порядке групп [Indexer::GROUPS]
+    domains: [Vec<Position>; 4],
+    /// Номера ячеек в списках domains
+    ranks: [HashMap<Position, usize>; 4],
+    /// Биномиальные коэффициенты: binomials[n][k] - количество сочетаний из n по k
+    binomials: Vec<Vec<usize>>,
+}
+
+impl Indexer {
+    /// Виды фигур в порядке, в котором их номера входят в индекс позиции
+    const GROUPS: [(Side, Piece); 4] = [
+        (Side::White, Piece::Man),
+        (Side::White, Piece::King),
+        (Side::Black, Piece::Man),
+        (Side::Black, Piece::King),
+    ];
+
+    fn new(variant: &Variant) -> Self {
+        let mut empty = GameData::new(variant.clone());
+        empty.white_pieces.clear();
+        empty.black_pieces.clear();
+        let squares: Vec<Position> = empty.playable_positions().collect();
+        let domains = Self::GROUPS.map(|(side, piece)| {
+            squares
+                .iter()
+                .copied()
+                // Шашка не может стоять на строке, на которой она становится дамкой
+                .filter(|position| piece.is_king() || !empty.is_promotion_row(side, *position))
+                .collect::<Vec<_>>()
+        });
+        let ranks = domains.clone().map(|domain| {
+            domain
+                .into_iter()
+                .enumerate()
+                .map(|(rank, position)| (position, rank))
+                .collect()
+        });
+        let max_pieces = Tablebase::MAX_PIECES as usize;
+        let binomials = (0..=squares.len())
+            .map(|n| {
+                let mut row = vec![1; max_pieces + 1];
+                for k in 1..=max_pieces {
+                    row[k] = if k > n {
+                        0
+                    } else {
+                        row[k - 1] * (n - k + 1) / k
+                    };
+                }
+                row
+            })
+            .collect();
+        Self {
+            empty,
+            domains,
+            ranks,
+            binomials,
+        }
+    }
+
+    /// Количество сочетаний ячеек для каждой группы фигур состава
+    fn group_sizes(&self, material: &Material) -> [usize; 4] {
+        let mut sizes = [0; 4];
+        for (group, (side, piece)) in Self::GROUPS.into_iter().enumerate() {
+            sizes[group] =
+                self.binomials[self.domains[group].len()][material.count(side, piece) as usize];
+        }
+        sizes
+    }
+
+    /// Количество индексов в таблице состава, включая невозможные расстановки
+    fn table_size(&self, material: &Material) -> usize {
+        self.group_sizes(material)
+            .into_iter()
+            .fold(2usize, |size, group_size| size.saturating_mul(group_size))
+    }
+
+    /// Возвращает индекс позиции в таблице её состава
+    fn index(&self, game_data: &GameData, material: &Material) -> Option<usize> {
+        let sizes = self.group_sizes(material);
+        let mut index = 0;
+        for (group, (side, piece)) in Self::GROUPS.into_iter().enumerate() {
+            let mut ranks = game_data
+                .pieces(side)
+                .iter()
+                .filter(|(_, side_piece)| **side_piece == piece)
+                .map(|(position, _)| self.ranks[group].get(position).copied())
+                .collect::<Option<Vec<usize>>>()?;
+            ranks.sort_unstable();
+            let combination: usize = ranks
+                .iter()
+                .enumerate()
+                .map(|(k, rank)| self.binomials[*rank][k + 1])
+                .sum();
+            index = index * sizes[group] + combination;
+        }
+        let side = match game_data.current_move {
+            Side::White => 0,
+            Side::Black => 1,
+        };
+        Some(index * 2 + side)
+    }
+
+    /// Восстанавливает позицию по индексу. None - если фигуры разных видов занимают одну ячейку
+    fn game_data(&self, material: &Material, index: usize) -> Option<GameData> {
+        let sizes = self.group_sizes(material);
+        let mut game_data = self.empty.clone();
+        game_data.current_move = if index.is_multiple_of(2) {
+            Side::White
+        } else {
+            Side::Black
+        };
+        let mut rest = index / 2;
+        let mut occupied = HashSet::new();
+        for (group, (side, piece)) in Self::GROUPS.into_iter().enumerate().rev() {
+            let mut combination = rest % sizes[group];
+            rest /= sizes[group];
+            let mut upper = self.domains[group].len();
+            for k in (1..=material.count(side, piece) as usize).rev() {
+                let mut rank = upper - 1;
+                while self.binomials[rank][k] > combination {
+                    rank -= 1;
+                }
+                combination -= self.binomials[rank][k];
+                upper = rank;
+                let position = self.domains[group][rank];
+                if !occupied.insert(position) {
+                    return None;
+                }
+                match side {
+                    Side::White => game_data.white_pieces.insert(position, piece),
+                    Side::Black => game_data.black_pieces.insert(position, piece),
+                };
+            }
+        }
+        Some(game_data)
+    }
+}
+
+/// Читает из потока массив байтов фиксированной длины
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
@@ -0,0 +1,45 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::structs::{evaluators::Accumulator, GameData, Move};
+
+/// Статическая оценка позиции, используемая движком при переборе
+pub trait Evaluator: Send + Sync {
+    /// Оценивает позицию с точки зрения стороны, которая сейчас ходит
+    ///
+    /// Положительное значение означает преимущество этой стороны
+    fn evaluate(&self, game_data: &GameData) -> i32;
+
+    /// Создаёт аккумулятор для позиции, если оценка умеет обновляться после хода,
+    /// а не вычисляться заново в каждой позиции перебора
+    fn new_accumulator(&self, _game_data: &GameData) -> Option<Accumulator> {
+        None
+    }
+
+    /// Обновляет аккумулятор после хода piece_move из позиции before, приведшего в позицию after
+    fn update_accumulator(
+        &self,
+        _accumulator: &mut Accumulator,
+        _before: &GameData,
+        _piece_move: &Move,
+        _after: &GameData,
+    ) {
+    }
+
+    /// Оценивает позицию по соответствующему ей аккумулятору
+    fn evaluate_accumulated(&self, _accumulator: &Accumulator, game_data: &GameData) -> i32 {
+        self.evaluate(game_data)
+    }
+}
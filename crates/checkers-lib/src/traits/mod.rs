@@ -0,0 +1,4 @@
+mod evaluator;
+mod ruleset;
+
+pub use self::{evaluator::Evaluator, ruleset::Ruleset};
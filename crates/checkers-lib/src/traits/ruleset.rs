@@ -0,0 +1,121 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::{
+    enums::{Direction, KingRange, Piece, Promotion, Side},
+    structs::{GameData, Move, Position},
+};
+
+/// Правила одной из разновидностей игры в шашки
+///
+/// Генератор ходов (см. [MoveGenerator](crate::structs::MoveGenerator)) не содержит знаний о правилах
+/// конкретной игры и опрашивает их через данный трейт
+pub trait Ruleset {
+    /// Размер игральной доски в клетках: (кол-во строк, кол-во столбцов)
+    fn board_size(&self) -> (i8, i8);
+
+    /// Количество строк, которые изначально занимают шашки каждой из сторон
+    fn initial_rows(&self) -> i8;
+
+    /// Направления, в которых может перемещаться шашка указанной стороны
+    fn man_movement_directions(&self, side: Side) -> Vec<Direction>;
+
+    /// Направления, в которых может бить шашка указанной стороны
+    fn man_taking_directions(&self, side: Side) -> Vec<Direction>;
+
+    /// Дальность хода дамки
+    fn king_range(&self) -> KingRange;
+
+    /// Обязательно ли взятие фигур противника, если оно возможно
+    fn is_taking_mandatory(&self) -> bool;
+
+    /// Правило большинства: обязательно ли выбирать взятие наибольшего количества фигур
+    fn is_majority_rule(&self) -> bool;
+
+    /// Поведение шашки, достигшей последней горизонтали во время взятия
+    fn promotion(&self) -> Promotion;
+
+    /// Сторона, которая делает первый ход
+    fn first_move(&self) -> Side {
+        Side::White
+    }
+
+    /// Направления, в которых может перемещаться и бить дамка
+    fn king_directions(&self) -> Vec<Direction> {
+        Vec::from(Direction::DIAGONAL)
+    }
+
+    /// Направления, в которых может бить дамка
+    fn king_taking_directions(&self) -> Vec<Direction> {
+        self.king_directions()
+    }
+
+    /// Сколько раз подряд можно перемещать дамки (без взятия), если у стороны есть и шашки
+    ///
+    /// None - ограничения нет
+    fn max_consecutive_king_moves(&self) -> Option<u32> {
+        None
+    }
+
+    /// Игра "в поддавки": выигрывает сторона, которая лишилась всех фигур или ходов
+    fn is_giveaway(&self) -> bool {
+        false
+    }
+
+    /// Может ли фигура piece взять фигуру противника enemy_piece
+    fn can_take(&self, _piece: Piece, _enemy_piece: Piece) -> bool {
+        true
+    }
+
+    /// Может ли фигура стоять на указанной ячейке доски
+    ///
+    /// По умолчанию игра идёт только на "черных" ячейках
+    fn is_playable_cell(&self, position: Position) -> bool {
+        (position.row + position.column) % 2 == 1
+    }
+
+    /// Возвращает начальное расположение шашек указанной стороны
+    fn initial_positions(&self, side: Side) -> Vec<Position> {
+        use itertools::Itertools;
+        let (rows, columns) = self.board_size();
+        let side_rows = match side {
+            Side::Black => 0..self.initial_rows(),
+            Side::White => rows - self.initial_rows()..rows,
+        };
+        side_rows
+            .cartesian_product(0..columns)
+            .map(Position::from)
+            .filter(|position| self.is_playable_cell(*position))
+            .collect()
+    }
+
+    /// Отбирает взятия, которые разрешено совершить, из всех возможных взятий стороны side
+    ///
+    /// По умолчанию учитывает только правило большинства
+    fn select_takings(&self, _game_data: &GameData, _side: Side, takings: Vec<Move>) -> Vec<Move> {
+        if !self.is_majority_rule() {
+            return takings;
+        }
+        let max_taken = takings
+            .iter()
+            .map(|taking| taking.taken_pieces_positions.len())
+            .max()
+            .unwrap_or(0);
+        takings
+            .into_iter()
+            .filter(|taking| taking.taken_pieces_positions.len() == max_taken)
+            .collect()
+    }
+}
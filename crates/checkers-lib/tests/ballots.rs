@@ -0,0 +1,56 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashSet;
+
+use checkers_lib::{
+    enums::{Side, Variant},
+    structs::BallotDeck,
+};
+
+/// Все дебюты колоды корректны и приводят к различным позициям
+#[test]
+fn english_deck_positions_are_distinct() {
+    let deck = BallotDeck::english();
+    // Колода составлена движком, в официальной колоде ACF 156 дебютов
+    assert_eq!(deck.ballots.len(), 174);
+    let positions: HashSet<_> = deck
+        .ballots
+        .iter()
+        .map(|ballot| {
+            let game_data = ballot.game_data(Variant::English);
+            let mut white: Vec<_> = game_data.white_pieces.into_iter().collect();
+            let mut black: Vec<_> = game_data.black_pieces.into_iter().collect();
+            white.sort_by_key(|(position, _)| (position.row, position.column));
+            black.sort_by_key(|(position, _)| (position.row, position.column));
+            (white, black)
+        })
+        .collect();
+    assert_eq!(positions.len(), deck.ballots.len());
+}
+
+/// Каждый дебют играется дважды, со сменой цвета
+#[test]
+fn english_deck_games_reverse_colours() {
+    let deck = BallotDeck::english();
+    let games: Vec<_> = deck.games().collect();
+    assert_eq!(games.len(), 2 * deck.ballots.len());
+    for pair in games.chunks(2) {
+        assert_eq!(pair[0].0, pair[1].0);
+        assert_eq!(pair[0].1, Side::Black);
+        assert_eq!(pair[1].1, Side::White);
+    }
+}
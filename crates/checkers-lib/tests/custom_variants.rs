@@ -0,0 +1,82 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::{CustomVariantError, Variant},
+    structs::rulesets::CustomRuleset,
+};
+use serde::{Deserialize, Serialize};
+
+/// Сохранённые данные с разновидностью игры, как в сохранённой партии
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Saved {
+    variant: Variant,
+}
+
+#[test]
+fn invalid_file_is_rejected() {
+    let error = |content: &str| CustomRuleset::parse_variants(content).unwrap_err();
+    assert!(matches!(
+        error("[[variant]]\nname = \"Большие\"\nboard_size = 40"),
+        CustomVariantError::InvalidBoardSize { board_size: 40, .. }
+    ));
+    assert!(matches!(
+        error("[[variant]]\nname = \"Без взятий\"\nmandatory_capture = false\nmajority_capture = true"),
+        CustomVariantError::MajorityRuleWithoutMandatoryTaking { .. }
+    ));
+    assert!(matches!(
+        error("[[variant]]\nname = \" \""),
+        CustomVariantError::EmptyName
+    ));
+    assert!(matches!(
+        error("[[variant]]\nname = \"Русские шашки\""),
+        CustomVariantError::DuplicateName(_)
+    ));
+    assert!(matches!(
+        error("[[variant]]\nname = \"Опечатка\"\nboard_sise = 10"),
+        CustomVariantError::Parse(_)
+    ));
+}
+
+#[test]
+fn saved_variant_is_validated() {
+    let [ruleset] = <[CustomRuleset; 1]>::try_from(
+        CustomRuleset::parse_variants(
+            "[[variant]]\nname = \" Малые \"\nboard_size = 6\ninitial_rows = 2",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(ruleset.name, "Малые");
+    let saved = Saved {
+        variant: Variant::Custom(ruleset.clone().into()),
+    };
+    let loaded: Saved = toml::from_str(&toml::to_string(&saved).unwrap()).unwrap();
+    assert_eq!(loaded, saved);
+
+    // Правила, которые не прошли бы проверку при создании, не загружаются и из сохранения
+    let invalid = Saved {
+        variant: Variant::Custom(
+            CustomRuleset {
+                initial_rows: 3,
+                ..ruleset
+            }
+            .into(),
+        ),
+    };
+    let error = toml::from_str::<Saved>(&toml::to_string(&invalid).unwrap()).unwrap_err();
+    assert!(error.message().contains("initial_rows"), "{}", error);
+}
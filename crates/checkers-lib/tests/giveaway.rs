@@ -0,0 +1,59 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::{Side, Variant},
+    structs::{Engine, GameData},
+};
+
+#[test]
+fn giveaway_reverses_result() {
+    // Белые берут последнюю шашку чёрных
+    for (variant, winner) in [
+        (Variant::Russian, Side::White),
+        (Variant::Giveaway, Side::Black),
+    ] {
+        let mut game_data = GameData::from_fen(variant, "W:W22:B18").unwrap();
+        let taking = game_data.get_all_available_moves()[0].clone();
+        game_data.make_move(Side::White, &taking);
+        assert!(game_data.pieces(Side::Black).is_empty());
+        assert_eq!(game_data.winner(), Some(winner));
+    }
+
+    // У белых нет ходов
+    for (variant, winner) in [
+        (Variant::Russian, Side::Black),
+        (Variant::Giveaway, Side::White),
+    ] {
+        let game_data = GameData::from_fen(variant, "W:W5:B1").unwrap();
+        assert!(game_data.get_all_available_moves().is_empty());
+        assert_eq!(game_data.winner(), Some(winner));
+    }
+
+    let game_data = GameData::new(Variant::Giveaway);
+    assert_eq!(game_data.winner(), None);
+}
+
+#[test]
+fn giveaway_evaluation_is_inverted() {
+    let engine = Engine::new(1);
+    let fen = "W:W21,22,23:B1";
+    let russian = engine.evaluate(&GameData::from_fen(Variant::Russian, fen).unwrap());
+    let giveaway = engine.evaluate(&GameData::from_fen(Variant::Giveaway, fen).unwrap());
+    // Лишние шашки выгодны в русских шашках и невыгодны в поддавках
+    assert!(russian > 0);
+    assert_eq!(giveaway, -russian);
+}
@@ -0,0 +1,138 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use checkers_lib::{
+    enums::{NetworkError, Side, Variant},
+    structs::{
+        evaluators::{Network, NetworkEvaluator},
+        Engine, GameData,
+    },
+    traits::Evaluator,
+};
+
+/// Сеть с детерминированными "случайными" весами
+fn network(variant: Variant, hidden: usize) -> Network {
+    let squares = GameData::new(variant).playable_positions().count();
+    let mut network = Network::new(squares, hidden);
+    let value = |index: usize| ((index * 37 + 11) % 101) as f32 / 100.0 - 0.5;
+    for (index, weight) in network.input_weights.iter_mut().enumerate() {
+        *weight = value(index) / 4.0;
+    }
+    for (index, bias) in network.hidden_biases.iter_mut().enumerate() {
+        *bias = 0.5 + value(index + 7) / 2.0;
+    }
+    for (index, weight) in network.output_weights.iter_mut().enumerate() {
+        *weight = value(index + 3);
+    }
+    network.output_bias = 0.1;
+    network
+}
+
+#[test]
+fn accumulator_updates_match_full_refresh() {
+    // Кроме начальных позиций - позиции, где скоро появятся дамки и начнутся взятия дамками
+    let positions = [
+        (Variant::Russian, None),
+        (Variant::International, None),
+        (Variant::Turkish, None),
+        (Variant::English, Some("W:W5,6,7,18,22,23:B14,15,19,20,24")),
+        (Variant::Russian, Some("B:W9,10,K22,23:B5,18,26,27,28")),
+    ];
+    for (variant, fen) in positions {
+        let evaluator = NetworkEvaluator::new(Arc::new(network(variant.clone(), 8)));
+        let engine = Engine::new(2);
+        let mut game_data = match fen {
+            Some(fen) => GameData::from_fen(variant.clone(), fen).unwrap(),
+            None => GameData::new(variant.clone()),
+        };
+        let mut accumulator = evaluator.new_accumulator(&game_data).unwrap();
+        for _ in 0..120 {
+            let Some(piece_move) = engine.search(&game_data).best_move else {
+                break;
+            };
+            let before = game_data.clone();
+            game_data.make_move(game_data.current_move, &piece_move);
+            evaluator.update_accumulator(&mut accumulator, &before, &piece_move, &game_data);
+
+            let refreshed = evaluator.new_accumulator(&game_data).unwrap();
+            for (updated, expected) in accumulator.values.iter().zip(&refreshed.values) {
+                assert!(
+                    (updated - expected).abs() < 1e-4,
+                    "{}: {} != {}",
+                    game_data.to_fen(),
+                    updated,
+                    expected
+                );
+            }
+            assert_eq!(
+                evaluator.evaluate_accumulated(&refreshed, &game_data),
+                evaluator.evaluate(&game_data)
+            );
+        }
+    }
+}
+
+#[test]
+fn score_is_from_side_to_move() {
+    let evaluator = NetworkEvaluator::new(Arc::new(network(Variant::Russian, 8)));
+    let mut white = GameData::new(Variant::Russian);
+    let mut black = white.clone();
+    white.current_move = Side::White;
+    black.current_move = Side::Black;
+    let network = &evaluator.network;
+    let output = |game_data: &GameData| {
+        let accumulator = network.accumulate(&network.active_inputs(game_data));
+        (network.output(&accumulator) * Network::OUTPUT_SCALE).round() as i32
+    };
+    assert_eq!(evaluator.evaluate(&white), output(&white));
+    assert_eq!(evaluator.evaluate(&black), -output(&black));
+
+    // Перебор с нейросетевой оценкой обновляет аккумулятор вместо полного вычисления
+    let engine = Engine::new(4).with_evaluator(Arc::new(evaluator));
+    assert!(engine.search(&white).best_move.is_some());
+}
+
+#[test]
+fn network_is_saved_and_loaded() {
+    let saved = network(Variant::English, 16);
+    let mut bytes = Vec::new();
+    saved.write_to(&mut bytes, &Variant::English).unwrap();
+    let loaded = Network::read_from(&mut bytes.as_slice(), Variant::English).unwrap();
+    assert_eq!(loaded, saved);
+
+    assert!(matches!(
+        Network::read_from(&mut bytes.as_slice(), Variant::Russian),
+        Err(NetworkError::VariantMismatch { .. })
+    ));
+    assert!(matches!(
+        Network::read_from(&mut &bytes[..bytes.len() - 1], Variant::English),
+        Err(NetworkError::Io(_))
+    ));
+    assert!(matches!(
+        Network::read_from(&mut &b"CKBK"[..], Variant::English),
+        Err(NetworkError::InvalidFormat(_))
+    ));
+
+    // В турецких шашках игровые - все 64 ячейки доски
+    let turkish = network(Variant::Turkish, 4);
+    assert_eq!(turkish.squares, 64);
+    let mut bytes = Vec::new();
+    turkish.write_to(&mut bytes, &Variant::Turkish).unwrap();
+    let loaded = Network::read_from(&mut bytes.as_slice(), Variant::Turkish).unwrap();
+    assert_eq!(loaded, turkish);
+}
@@ -0,0 +1,121 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use checkers_lib::{
+    enums::{GameResult, PdnError, Variant},
+    structs::{rulesets::CustomRuleset, Engine, GameData, GameRecord, OpeningBook},
+};
+
+const GAMES: &str = r#"
+[Event "Первая партия"]
+[GameType "21"]
+[Result "1-0"]
+1. 11-15 23-19 2. 8-11 {комментарий} 22-17 (2... 22-18 15x22) 3. 9-13 17-14 1-0
+
+[Event "Вторая партия"]
+1.11-15 24-20 2.8-11?! 28-24 1/2-1/2
+
+[Event "Третья партия"]
+1. 9-14 23-18 2. 14x23 27x18 0-1
+
+[GameType "20"]
+1. 32-28 19-23 *
+"#;
+
+#[test]
+fn pdn_collection_is_parsed() {
+    let games = GameRecord::parse_pdn(&Variant::English, GAMES);
+    assert_eq!(games.len(), 4);
+    let first = games[0].as_ref().unwrap();
+    assert_eq!(
+        first.notation(),
+        ["11-15", "23-19", "8-11", "22-17", "9-13", "17-14"]
+    );
+    assert_eq!(first.result(), GameResult::WhiteWins);
+    assert_eq!(
+        first.tags,
+        [("Event".to_string(), "Первая партия".to_string())]
+    );
+    assert!(first.to_pdn(&[]).starts_with("[Event \"Первая партия\"]\n"));
+    assert_eq!(games[1].as_ref().unwrap().result(), GameResult::Draw);
+    assert_eq!(
+        games[3].as_ref().unwrap().initial.variant,
+        Variant::International
+    );
+
+    let illegal = GameRecord::parse_pdn(&Variant::English, "1. 11-15 11-15 *");
+    assert!(matches!(
+        illegal[0],
+        Err(PdnError::IllegalMove { ply: 2, .. })
+    ));
+}
+
+#[test]
+fn book_is_built_from_games() {
+    let mut book = OpeningBook::new(Variant::English);
+    let added = GameRecord::parse_pdn(&Variant::English, GAMES)
+        .into_iter()
+        .filter(|game| book.add_game(game.as_ref().unwrap(), 4))
+        .count();
+    // Партия в международные шашки в книгу не попадает
+    assert_eq!(added, 3);
+
+    let initial = GameData::new(Variant::English);
+    let moves = book.moves(&initial);
+    assert_eq!(moves.len(), 2);
+    assert_eq!(moves[0].notation, "11-15");
+    assert_eq!(
+        (
+            moves[0].games,
+            moves[0].white_wins,
+            moves[0].draws,
+            moves[0].black_wins
+        ),
+        (2, 1, 1, 0)
+    );
+
+    // Чёрные начинают партию, поэтому ход 9-14, после которого они выиграли, весит больше
+    let engine = Engine::new(1).with_book(Arc::new(book));
+    let best_move = engine.best_move(&initial).unwrap();
+    assert_eq!(initial.move_notation(&best_move), "9-14");
+
+    let mut bytes = Vec::new();
+    engine.book().unwrap().write_to(&mut bytes).unwrap();
+    let loaded = OpeningBook::read_from(&mut bytes.as_slice(), Variant::English).unwrap();
+    assert_eq!(loaded.moves(&initial), moves);
+}
+
+#[test]
+fn file_name_stays_in_data_directory() {
+    assert_eq!(
+        OpeningBook::default_file_name(&Variant::English),
+        "Английские шашки.ckbk"
+    );
+    let rulesets = CustomRuleset::parse_variants(
+        r#"
+        [[variant]]
+        name = "../../etc/Мои: шашки"
+        "#,
+    )
+    .unwrap();
+    let variant = Variant::Custom(rulesets[0].clone().into());
+    assert_eq!(
+        OpeningBook::default_file_name(&variant),
+        "______etc_Мои_ шашки.ckbk"
+    );
+}
@@ -0,0 +1,230 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::{Side, Variant},
+    structs::{rulesets::CustomRuleset, GameData, MoveGenerator},
+};
+
+/// Сравнивает количество позиций, достижимых из начальной, с эталонными значениями
+fn assert_perft(variant: Variant, expected: &[u64]) {
+    let game_data = GameData::new(variant.clone());
+    for (depth, expected_nodes) in (1..).zip(expected) {
+        assert_eq!(
+            game_data.perft(depth),
+            *expected_nodes,
+            "{variant}: неверное количество позиций на глубине {depth}"
+        );
+    }
+}
+
+/// Возвращает ходы стороны side в позиции FEN в числовой нотации, по возрастанию
+///
+/// Сторона может не совпадать со стороной, которая ходит в позиции
+fn moves(variant: Variant, fen: &str, side: Side) -> Vec<String> {
+    let game_data = GameData::from_fen(variant, fen).unwrap();
+    let mut moves: Vec<String> = MoveGenerator::new(&game_data, game_data.ruleset())
+        .get_available_moves(side)
+        .iter()
+        .map(|piece_move| game_data.move_notation(piece_move))
+        .collect();
+    moves.sort();
+    moves
+}
+
+#[test]
+fn english_perft() {
+    assert_perft(Variant::English, &[7, 49, 302, 1469, 7361, 36768]);
+}
+
+#[test]
+fn russian_perft() {
+    assert_perft(Variant::Russian, &[7, 49, 302, 1469, 7482, 37986]);
+}
+
+#[test]
+fn international_perft() {
+    assert_perft(Variant::International, &[9, 81, 658, 4265]);
+}
+
+#[test]
+fn italian_perft() {
+    assert_perft(Variant::Italian, &[7, 49, 302, 1469, 7361, 36473]);
+}
+
+#[test]
+fn italian_capture_priority() {
+    // Шашка не бьёт дамку
+    assert_eq!(
+        moves(Variant::Italian, "W:W22:BK18", Side::White),
+        ["22-17"]
+    );
+    // Бить нужно дамкой, а не шашкой
+    assert_eq!(
+        moves(Variant::Italian, "W:W22,K23:B18", Side::White),
+        ["23x14"]
+    );
+    // Ходы стороны, которая сейчас не ходит, отбираются по её фигурам
+    assert_eq!(
+        moves(Variant::Italian, "W:W16:B11,K12", Side::Black),
+        ["12x19"]
+    );
+}
+
+#[test]
+fn spanish_perft() {
+    assert_perft(Variant::Spanish, &[7, 49, 302, 1469, 7361, 36473]);
+    assert_perft(Variant::Portuguese, &[7, 49, 302, 1469, 7361, 36473]);
+}
+
+#[test]
+fn spanish_capture_priority() {
+    for variant in [Variant::Spanish, Variant::Portuguese] {
+        // При равном количестве фигур нужно взять дамку
+        assert_eq!(
+            moves(variant.clone(), "W:W22:B17,K18", Side::White),
+            ["22x15"]
+        );
+        // Но больше фигур важнее, чем дамка
+        assert_eq!(
+            moves(variant.clone(), "W:W22:B9,17,K18", Side::White),
+            ["22x13x6"]
+        );
+        // Ходы стороны, которая сейчас не ходит, отбираются по её фигурам
+        assert_eq!(moves(variant, "W:W15,K16,30:B11", Side::Black), ["11x20"]);
+    }
+}
+
+#[test]
+fn frisian_perft() {
+    assert_perft(Variant::Frisian, &[9, 81, 658, 3874]);
+}
+
+#[test]
+fn frisian_capture_value() {
+    // Дамка ценнее шашки
+    assert_eq!(
+        moves(Variant::Frisian, "W:W33:BK28,29", Side::White),
+        ["33x22"]
+    );
+    // Но две шашки ценнее дамки
+    assert_eq!(
+        moves(Variant::Frisian, "W:W33:B20,K28,29", Side::White),
+        ["33x24x15"]
+    );
+    // Ходы стороны, которая сейчас не ходит, отбираются по её фигурам
+    assert_eq!(
+        moves(Variant::Frisian, "W:WK22,23:B18", Side::Black),
+        ["18x27"]
+    );
+}
+
+#[test]
+fn frisian_consecutive_king_moves() {
+    let mut game_data = GameData::from_fen(Variant::Frisian, "W:W33,K46:B1,2,3").unwrap();
+    let king_moves = |game_data: &GameData| {
+        game_data
+            .get_all_available_moves()
+            .into_iter()
+            .filter(|piece_move| game_data.pieces(Side::White)[&piece_move.from].is_king())
+            .collect::<Vec<_>>()
+    };
+    for _ in 0..3 {
+        let king_move = king_moves(&game_data)[0].clone();
+        game_data.make_move(Side::White, &king_move);
+        let reply = game_data.get_all_available_moves()[0].clone();
+        game_data.make_move(Side::Black, &reply);
+    }
+    // После трёх ходов дамкой подряд нужно пойти шашкой
+    assert_eq!(game_data.consecutive_king_moves(Side::White), 3);
+    assert!(king_moves(&game_data).is_empty());
+    assert!(!game_data.get_all_available_moves().is_empty());
+
+    let man_move = game_data.get_all_available_moves()[0].clone();
+    game_data.make_move(Side::White, &man_move);
+    assert_eq!(game_data.consecutive_king_moves(Side::White), 0);
+    let reply = game_data.get_all_available_moves()[0].clone();
+    game_data.make_move(Side::Black, &reply);
+    assert!(!king_moves(&game_data).is_empty());
+
+    // Без шашек ограничения нет
+    let mut kings_only = GameData::from_fen(Variant::Frisian, "W:WK46:B1,2,3").unwrap();
+    for _ in 0..4 {
+        let king_move = kings_only.get_all_available_moves()[0].clone();
+        kings_only.make_move(Side::White, &king_move);
+        let reply = kings_only.get_all_available_moves()[0].clone();
+        kings_only.make_move(Side::Black, &reply);
+    }
+    assert!(!kings_only.get_all_available_moves().is_empty());
+}
+
+#[test]
+fn turkish_perft() {
+    assert_perft(Variant::Turkish, &[8, 64, 708, 7538]);
+}
+
+#[test]
+fn turkish_man_captures_sideways() {
+    // Шашка бьёт вбок, но не назад
+    assert_eq!(
+        moves(Variant::Turkish, "W:W36:B37,44", Side::White),
+        ["36x38"]
+    );
+}
+
+#[test]
+fn canadian_perft() {
+    assert_perft(Variant::Canadian, &[11, 121, 1222, 10053]);
+}
+
+#[test]
+fn canadian_man_captures_backwards() {
+    assert_eq!(
+        moves(Variant::Canadian, "W:W39:B45", Side::White),
+        ["39x50"]
+    );
+}
+
+#[test]
+fn brazilian_perft() {
+    assert_perft(Variant::Brazilian, &[7, 49, 302, 1469, 7473, 37628]);
+}
+
+#[test]
+fn custom_variants_perft() {
+    let rulesets = CustomRuleset::parse_variants(
+        r#"
+        [[variant]]
+        name = "Как русские"
+
+        [[variant]]
+        name = "Как английские"
+        men_capture_backwards = false
+        flying_kings = false
+        promotion_continues_move = false
+        "#,
+    )
+    .unwrap();
+    let [russian, english] = <[CustomRuleset; 2]>::try_from(rulesets).unwrap();
+    assert_perft(
+        Variant::Custom(russian.into()),
+        &[7, 49, 302, 1469, 7482, 37986],
+    );
+    assert_perft(
+        Variant::Custom(english.into()),
+        &[7, 49, 302, 1469, 7361, 36768],
+    );
+}
@@ -0,0 +1,66 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{enums::Direction, structs::Position};
+
+#[test]
+fn orthogonal_neighbours() {
+    let position = Position { row: 3, column: 4 };
+    assert_eq!(
+        position.orthogonal_neighbours(2),
+        vec![
+            (Position { row: 2, column: 4 }, Direction::Up),
+            (Position { row: 4, column: 4 }, Direction::Down),
+            (Position { row: 3, column: 3 }, Direction::Left),
+            (Position { row: 3, column: 5 }, Direction::Right),
+            (Position { row: 1, column: 4 }, Direction::Up),
+            (Position { row: 5, column: 4 }, Direction::Down),
+            (Position { row: 3, column: 2 }, Direction::Left),
+            (Position { row: 3, column: 6 }, Direction::Right),
+        ]
+    );
+    // Соседи совпадают с ячейками, в которые ведут ортогональные направления
+    for (neighbour, direction) in position.orthogonal_neighbours(1) {
+        assert!(Direction::ORTHOGONAL.contains(&direction));
+        assert_eq!(position.next(direction), neighbour);
+    }
+}
+
+#[test]
+fn neighbours_in_all_directions() {
+    let position = Position { row: 4, column: 4 };
+    let neighbours = position.neighbours(3);
+    assert_eq!(neighbours.len(), 3 * 8);
+    assert_eq!(
+        neighbours[..4],
+        position.diagonal_neighbours(3)[..4],
+        "сначала идут соседи по диагоналям"
+    );
+    for direction in Direction::ALL {
+        let mut expected = position;
+        let mut found = Vec::new();
+        for _ in 0..3 {
+            expected = expected.next(direction);
+            found.push(expected);
+        }
+        let in_direction: Vec<Position> = neighbours
+            .iter()
+            .filter(|(_, neighbour_direction)| *neighbour_direction == direction)
+            .map(|(neighbour, _)| *neighbour)
+            .collect();
+        assert_eq!(in_direction, found, "{:?}", direction);
+    }
+}
@@ -0,0 +1,93 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::{FenError, PositionViolation, Side, Variant},
+    structs::{GameData, Position},
+};
+
+fn violations(fen: &str) -> Vec<PositionViolation> {
+    match GameData::from_fen(Variant::English, fen) {
+        Err(FenError::IllegalPosition(violations)) => violations,
+        result => panic!("позиция {fen} должна быть недопустимой: {result:?}"),
+    }
+}
+
+#[test]
+fn initial_position_is_valid() {
+    assert!(GameData::new(Variant::English).validate().is_ok());
+    assert!(GameData::from_fen(Variant::English, "B:W21-32:B1-12").is_ok());
+}
+
+#[test]
+fn man_on_promotion_row() {
+    assert_eq!(
+        violations("W:W1,21:B12"),
+        vec![PositionViolation::ManOnPromotionRow {
+            side: Side::White,
+            position: Position { row: 0, column: 1 },
+        }]
+    );
+    assert!(GameData::from_fen(Variant::English, "W:WK1,21:B12").is_ok());
+}
+
+#[test]
+fn too_many_pieces() {
+    assert_eq!(
+        violations("W:W13-32:B1-5"),
+        vec![PositionViolation::TooManyPieces {
+            side: Side::White,
+            count: 20,
+            max: 12,
+        }]
+    );
+}
+
+#[test]
+fn piece_on_both_sides_and_missing_side() {
+    assert_eq!(
+        violations("W:W18:B18"),
+        vec![PositionViolation::OccupiedByBothSides(Position {
+            row: 4,
+            column: 3,
+        })]
+    );
+    // Позиция без фигур у одной из сторон возникает в конце партии, но начать с неё нельзя
+    let finished = GameData::from_fen(Variant::English, "W:W18:B").unwrap();
+    assert!(finished.validate().is_ok());
+    assert_eq!(
+        finished.validate_start(),
+        Err(vec![PositionViolation::NoPieces(Side::Black)])
+    );
+    assert!(GameData::new(Variant::English).validate_start().is_ok());
+}
+
+#[test]
+fn unplayable_cell() {
+    let mut game_data = GameData::new(Variant::English);
+    let position = Position { row: 4, column: 4 };
+    game_data.white_pieces.insert(position, Default::default());
+    game_data
+        .white_pieces
+        .remove(&Position { row: 5, column: 0 });
+    assert_eq!(
+        game_data.validate(),
+        Err(vec![PositionViolation::UnplayableCell {
+            side: Side::White,
+            position,
+        }])
+    );
+}
@@ -0,0 +1,105 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::{Arc, OnceLock};
+
+use checkers_lib::{
+    enums::{TablebaseError, TablebaseValue, Variant},
+    structs::{rulesets::CustomRuleset, Engine, GameData, Tablebase},
+};
+
+/// Трёхфигурные таблицы английских шашек, общие для всех тестов, так как строятся долго
+fn english_tablebase() -> Arc<Tablebase> {
+    static TABLEBASE: OnceLock<Arc<Tablebase>> = OnceLock::new();
+    TABLEBASE
+        .get_or_init(|| Arc::new(Tablebase::generate(Variant::English, 3, |_| {}).unwrap()))
+        .clone()
+}
+
+#[test]
+fn two_kings_win_against_one() {
+    let tablebase = english_tablebase();
+    let probe = |fen: &str| tablebase.probe(&GameData::from_fen(Variant::English, fen).unwrap());
+
+    assert!(matches!(
+        probe("W:WK1,K2:BK32"),
+        Some(TablebaseValue::Win(_))
+    ));
+    assert!(matches!(
+        probe("B:WK1,K2:BK32"),
+        Some(TablebaseValue::Loss(_))
+    ));
+    assert_eq!(probe("W:WK1:BK32"), Some(TablebaseValue::Draw));
+    // Позиции с большим количеством фигур в таблицах отсутствуют
+    assert_eq!(probe("W:WK1,K2:BK31,K32"), None);
+
+    // Лучший ход сохраняет выигрыш и приближает конец партии
+    let game_data = GameData::from_fen(Variant::English, "W:WK1,K2:BK32").unwrap();
+    let (best_move, value) = tablebase.best_move(&game_data).unwrap();
+    assert_eq!(probe("W:WK1,K2:BK32"), Some(value));
+    let mut child = game_data.clone();
+    child.make_move(game_data.current_move, &best_move);
+    assert_eq!(tablebase.probe(&child).unwrap().for_previous_move(), value);
+}
+
+#[test]
+fn engine_uses_tablebase() {
+    let engine = Engine::new(2).with_tablebase(english_tablebase());
+    let game_data = GameData::from_fen(Variant::English, "W:WK1,K2:BK32").unwrap();
+    let result = engine.search(&game_data);
+    assert!(Engine::is_win_score(result.score) && result.score > 0);
+}
+
+#[test]
+fn tablebase_file_round_trip() {
+    let tablebase = english_tablebase();
+    let mut bytes = Vec::new();
+    tablebase.write_to(&mut bytes).unwrap();
+
+    let loaded = Tablebase::read_from(&mut bytes.as_slice(), Variant::English).unwrap();
+    for fen in ["W:WK1,K2:BK32", "B:W21:B12", "B:WK22:B3,4"] {
+        let game_data = GameData::from_fen(Variant::English, fen).unwrap();
+        assert_eq!(
+            loaded.probe(&game_data),
+            tablebase.probe(&game_data),
+            "{fen}"
+        );
+    }
+    assert!(matches!(
+        Tablebase::read_from(&mut bytes.as_slice(), Variant::Russian),
+        Err(TablebaseError::VariantMismatch { .. })
+    ));
+}
+
+#[test]
+fn file_name_stays_in_data_directory() {
+    assert_eq!(
+        Tablebase::default_file_name(&Variant::English),
+        "Английские шашки.cktb"
+    );
+    let rulesets = CustomRuleset::parse_variants(
+        r#"
+        [[variant]]
+        name = "../../etc/Мои: шашки"
+        "#,
+    )
+    .unwrap();
+    let variant = Variant::Custom(rulesets[0].clone().into());
+    assert_eq!(
+        Tablebase::default_file_name(&variant),
+        "______etc_Мои_ шашки.cktb"
+    );
+}
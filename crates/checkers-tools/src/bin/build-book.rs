@@ -0,0 +1,118 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Составление дебютной книги по сборнику партий в формате PDN
+//!
+//! Использование: build-book <правила> <файлы PDN...> [--plies N] [--output файл]
+
+use std::{env, fs, process::ExitCode};
+
+use checkers_lib::structs::{GameRecord, OpeningBook};
+use checkers_tools::find_variant;
+
+/// Сколько первых полуходов каждой партии попадает в книгу по умолчанию
+const DEFAULT_PLIES: usize = 20;
+/// Сколько ошибок разбора партий выводится, остальные только подсчитываются
+const MAX_REPORTED_ERRORS: usize = 10;
+
+const USAGE: &str =
+    "Использование: build-book <правила> <файлы PDN...> [--plies N] [--output файл]";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let Some(name) = args.next() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let variant = match find_variant(&name) {
+        Ok(variant) => variant,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut plies = DEFAULT_PLIES;
+    let mut output = OpeningBook::default_file_name(&variant);
+    let mut files = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--plies" => match args.next().and_then(|plies| plies.parse().ok()) {
+                Some(value) => plies = value,
+                None => {
+                    eprintln!("После --plies должно быть указано число полуходов");
+                    return ExitCode::FAILURE;
+                }
+            },
+            "--output" => match args.next() {
+                Some(value) => output = value,
+                None => {
+                    eprintln!("После --output должен быть указан файл");
+                    return ExitCode::FAILURE;
+                }
+            },
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let mut book = OpeningBook::new(variant.clone());
+    let (mut added, mut skipped, mut errors) = (0, 0, 0);
+    for file in &files {
+        let pdn = match fs::read_to_string(file) {
+            Ok(pdn) => pdn,
+            Err(error) => {
+                eprintln!("Не удалось прочитать {}: {}", file, error);
+                return ExitCode::FAILURE;
+            }
+        };
+        for (index, game) in GameRecord::parse_pdn(&variant, &pdn)
+            .into_iter()
+            .enumerate()
+        {
+            match game {
+                Ok(record) if book.add_game(&record, plies) => added += 1,
+                // Партии других разновидностей игры
+                Ok(_) => skipped += 1,
+                Err(error) => {
+                    errors += 1;
+                    if errors <= MAX_REPORTED_ERRORS {
+                        eprintln!("{}, партия {}: {}", file, index + 1, error);
+                    }
+                }
+            }
+        }
+    }
+    println!(
+        "Партий добавлено: {}, других правил: {}, с ошибками: {}. Позиций в книге: {}",
+        added,
+        skipped,
+        errors,
+        book.len()
+    );
+    match book.save(&output) {
+        Ok(()) => {
+            println!("Книга сохранена в {}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,62 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Построение таблиц эндшпиля
+//!
+//! Использование: generate-tablebase <правила> <количество фигур> [файл]
+
+use std::{env, process::ExitCode, time::Instant};
+
+use checkers_lib::structs::Tablebase;
+use checkers_tools::find_variant;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (Some(name), Some(max_pieces)) = (args.first(), args.get(1)) else {
+        eprintln!("Использование: generate-tablebase <правила> <количество фигур> [файл]");
+        return ExitCode::FAILURE;
+    };
+    let variant = match find_variant(name) {
+        Ok(variant) => variant,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Ok(max_pieces) = max_pieces.parse::<u32>() else {
+        eprintln!("Количество фигур должно быть числом: \"{}\"", max_pieces);
+        return ExitCode::FAILURE;
+    };
+    let path = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| Tablebase::default_file_name(&variant));
+
+    let start = Instant::now();
+    let tablebase = Tablebase::generate(variant, max_pieces, |material| {
+        println!("[{:>8.1?}] {}", start.elapsed(), material);
+    });
+    match tablebase.and_then(|tablebase| tablebase.save(&path)) {
+        Ok(()) => {
+            println!("Таблицы сохранены в {} за {:.1?}", path, start.elapsed());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,131 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Партии движка с самим собой для составления набора позиций с оценками и результатами
+//!
+//! Использование: self-play <правила> <количество партий> [--depth N] [--noise N]
+//! [--openings ballots|random] [--random-plies N] [--max-plies N] [--threads N] [--seed N]
+//! [--output файл]
+
+use std::{env, process::ExitCode, str::FromStr, time::Instant};
+
+use checkers_lib::structs::Engine;
+use checkers_tools::{
+    enums::{SelfPlayError, SelfPlayOpenings},
+    find_variant,
+    structs::{Dataset, SelfPlay},
+};
+
+const USAGE: &str = "Использование: self-play <правила> <количество партий> [--depth N] [--noise N] [--openings ballots|random] [--random-plies N] [--max-plies N] [--threads N] [--seed N] [--output файл]";
+
+/// Разбирает числовое значение параметра командной строки
+fn parse_value<T: FromStr>(name: &str, value: Option<String>) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("После {} должно быть указано число", name))
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut args = env::args().skip(1);
+    let (Some(name), Some(games)) = (args.next(), args.next()) else {
+        return Err(USAGE.to_string());
+    };
+    let variant = find_variant(&name)?;
+    let games: usize = games
+        .parse()
+        .map_err(|_| format!("Количество партий должно быть числом: \"{}\"", games))?;
+    let mut depth = Engine::DEFAULT_DEPTH;
+    let mut noise = 0;
+    let mut openings = None;
+    let mut random_plies = SelfPlay::DEFAULT_RANDOM_PLIES;
+    let mut max_plies = SelfPlay::DEFAULT_MAX_PLIES;
+    let mut threads = 1;
+    let mut seed: u64 = rand::random();
+    let mut output = Dataset::default_file_name(&variant);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--depth" => depth = parse_value(&arg, args.next())?,
+            "--noise" => noise = parse_value(&arg, args.next())?,
+            "--random-plies" => random_plies = parse_value(&arg, args.next())?,
+            "--max-plies" => max_plies = parse_value(&arg, args.next())?,
+            "--threads" => threads = parse_value(&arg, args.next())?,
+            "--seed" => seed = parse_value(&arg, args.next())?,
+            "--openings" => {
+                openings = Some(
+                    args.next()
+                        .as_deref()
+                        .and_then(SelfPlayOpenings::parse)
+                        .ok_or("После --openings должно быть указано ballots или random")?,
+                );
+            }
+            "--output" => {
+                output = args
+                    .next()
+                    .ok_or("После --output должен быть указан файл")?
+            }
+            _ => return Err(format!("Неизвестный параметр \"{}\"\n{}", arg, USAGE)),
+        }
+    }
+    if depth == 0 {
+        return Err(SelfPlayError::ZeroDepth.to_string());
+    }
+
+    let mut self_play = SelfPlay::new(variant.clone(), Engine::new(depth));
+    if let Some(openings) = openings {
+        if openings == SelfPlayOpenings::Ballots && !self_play.has_ballots() {
+            return Err(format!(
+                "Для правил \"{}\" нет колоды дебютов, используйте --openings random",
+                variant
+            ));
+        }
+        self_play.openings = openings;
+    }
+    self_play.noise = noise;
+    self_play.random_plies = random_plies;
+    self_play.max_plies = max_plies;
+
+    println!(
+        "Партий: {}, глубина: {}, шум: {}, начальное число генератора: {}",
+        games, depth, self_play.noise, seed
+    );
+    let start = Instant::now();
+    let dataset = self_play
+        .generate(games, threads, seed, |finished, positions| {
+            println!(
+                "[{}/{}] позиций без повторов: {}",
+                finished, games, positions
+            );
+        })
+        .map_err(|error| error.to_string())?;
+    dataset.save(&output).map_err(|error| error.to_string())?;
+    println!(
+        "Набор из {} позиций сохранён в {} за {:.1} с",
+        dataset.entries.len(),
+        output,
+        start.elapsed().as_secs_f64()
+    );
+    Ok(())
+}
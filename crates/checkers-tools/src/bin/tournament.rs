@@ -0,0 +1,131 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Турнир движков: круговой или один против всех, с дебютами из колоды и сменой цвета,
+//! или последовательный тест (SPRT) двух участников
+//!
+//! Использование: tournament <файл настроек TOML>
+
+use std::{env, fs, process::ExitCode};
+
+use checkers_lib::enums::Side;
+use checkers_tools::{
+    enums::SprtStatus,
+    structs::{Crosstable, MatchScore, Tournament, TournamentConfig},
+};
+
+fn main() -> ExitCode {
+    let Some(path) = env::args().nth(1) else {
+        eprintln!("Использование: tournament <файл настроек TOML>");
+        return ExitCode::FAILURE;
+    };
+    let tournament = match TournamentConfig::load(&path).and_then(Tournament::new) {
+        Ok(tournament) => tournament,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let games = tournament.schedule();
+    println!(
+        "{}: {}, участников: {}, партий: {}, {}",
+        tournament.config.event,
+        tournament.variant,
+        tournament.config.engines.len(),
+        games.len(),
+        match tournament.time_control {
+            Some(time_control) => format!("контроль времени {}", time_control),
+            None => "игра на заданную глубину".to_string(),
+        }
+    );
+    let names = tournament.names();
+    let mut crosstable = Crosstable::new(names.clone());
+    if let Some(sprt) = tournament.config.sprt {
+        let (lower, upper) = sprt.bounds();
+        println!(
+            "{}: {} против {}, границы LLR [{:.2}, {:.2}]",
+            sprt, names[0], names[1], lower, upper
+        );
+    }
+    let mut finished = 0;
+    // Решение SPRT, принятое после партии, на которой LLR вышел за границу, и счёт на этот
+    // момент. Партии, доигранные после решения, попадают только в таблицу
+    let mut sprt_status = SprtStatus::Continue;
+    let mut sprt_score = MatchScore::default();
+    let played = tournament.run(&games, |played| {
+        finished += 1;
+        crosstable.add(played);
+        let sprt_progress = match tournament.config.sprt {
+            Some(sprt) if sprt_status == SprtStatus::Continue => {
+                sprt_score = crosstable.scores[0][1];
+                sprt_status = sprt.status(&sprt_score);
+                format!(", LLR {:.2}", sprt.llr(&sprt_score))
+            }
+            _ => String::new(),
+        };
+        println!(
+            "[{}/{}] Партия {}: {} - {}, {}{}",
+            finished,
+            games.len(),
+            played.game.number,
+            names[played.game.player(Side::White)],
+            names[played.game.player(Side::Black)],
+            played.record.result().pdn(),
+            sprt_progress
+        );
+        sprt_status == SprtStatus::Continue
+    });
+
+    let pdn: Vec<String> = played
+        .iter()
+        .map(|played| played.record.to_pdn(&[]))
+        .collect();
+    if let Err(error) = fs::write(&tournament.config.pdn, pdn.join("\n")) {
+        eprintln!(
+            "Не удалось записать партии в {}: {}",
+            tournament.config.pdn, error
+        );
+        return ExitCode::FAILURE;
+    }
+    let mut report = crosstable.to_string();
+    if let Some(sprt) = tournament.config.sprt {
+        let (lower, upper) = sprt.bounds();
+        report.push_str(&format!(
+            "\n{}: LLR {:.2} [{:.2}, {:.2}] после {} партий ({}), {}\n",
+            sprt,
+            sprt.llr(&sprt_score),
+            lower,
+            upper,
+            sprt_score.games(),
+            sprt_score,
+            sprt_status
+        ));
+    }
+    println!("\n{}", report);
+    if let Err(error) = fs::write(&tournament.config.report, &report) {
+        eprintln!(
+            "Не удалось записать таблицу в {}: {}",
+            tournament.config.report, error
+        );
+        return ExitCode::FAILURE;
+    }
+    println!(
+        "Партии записаны в {}, таблица - в {}",
+        tournament.config.pdn, tournament.config.report
+    );
+    ExitCode::SUCCESS
+}
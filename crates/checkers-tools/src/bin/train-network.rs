@@ -0,0 +1,159 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Обучение нейросети оценки позиции по наборам позиций, составленным утилитой self-play
+//!
+//! Использование: train-network <правила> <наборы позиций...> [--hidden N] [--epochs N]
+//! [--lambda X] [--learning-rate X] [--batch-size N] [--network файл] [--seed N] [--output файл]
+
+use std::{env, process::ExitCode, str::FromStr};
+
+use checkers_lib::structs::{evaluators::Network, GameData};
+use checkers_tools::{
+    find_variant,
+    structs::{Dataset, NetworkTrainer, TrainingSample},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+const USAGE: &str = "Использование: train-network <правила> <наборы позиций...> [--hidden N] [--epochs N] [--lambda X] [--learning-rate X] [--batch-size N] [--network файл] [--seed N] [--output файл]";
+
+/// Разбирает числовое значение параметра командной строки
+fn parse_value<T: FromStr>(name: &str, value: Option<String>) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("После {} должно быть указано число", name))
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut args = env::args().skip(1);
+    let Some(name) = args.next() else {
+        return Err(USAGE.to_string());
+    };
+    let variant = find_variant(&name)?;
+    let mut hidden = NetworkTrainer::DEFAULT_HIDDEN;
+    let mut epochs = NetworkTrainer::DEFAULT_EPOCHS;
+    let mut lambda = NetworkTrainer::DEFAULT_LAMBDA;
+    let mut learning_rate = NetworkTrainer::DEFAULT_LEARNING_RATE;
+    let mut batch_size = NetworkTrainer::DEFAULT_BATCH_SIZE;
+    let mut initial = None;
+    let mut seed: u64 = rand::random();
+    let mut output = Network::default_file_name(&variant);
+    let mut files = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--hidden" => hidden = parse_value(&arg, args.next())?,
+            "--epochs" => epochs = parse_value(&arg, args.next())?,
+            "--lambda" => lambda = parse_value(&arg, args.next())?,
+            "--learning-rate" => learning_rate = parse_value(&arg, args.next())?,
+            "--batch-size" => batch_size = parse_value(&arg, args.next())?,
+            "--seed" => seed = parse_value(&arg, args.next())?,
+            "--network" => {
+                let path = args
+                    .next()
+                    .ok_or("После --network должен быть указан файл")?;
+                initial = Some(
+                    Network::load(&path, variant.clone())
+                        .map_err(|error| format!("{}: {}", path, error))?,
+                );
+            }
+            "--output" => {
+                output = args
+                    .next()
+                    .ok_or("После --output должен быть указан файл")?
+            }
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    if !(0.0..=1.0).contains(&lambda) {
+        return Err("Значение --lambda должно быть от 0 до 1".to_string());
+    }
+    if hidden == 0 || hidden > u16::MAX as usize {
+        return Err(format!(
+            "Размер скрытого слоя должен быть от 1 до {}",
+            u16::MAX
+        ));
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let network = match initial {
+        Some(network) => network,
+        None => {
+            let squares = GameData::new(variant.clone()).playable_positions().count();
+            NetworkTrainer::random_network(squares, hidden, &mut rng)
+        }
+    };
+    let mut samples = Vec::new();
+    for file in &files {
+        let dataset =
+            Dataset::load(file, variant.clone()).map_err(|error| format!("{}: {}", file, error))?;
+        samples.extend(
+            dataset
+                .entries
+                .iter()
+                .filter_map(|entry| TrainingSample::from_entry(entry, &variant, &network, lambda)),
+        );
+    }
+    let mut trainer = NetworkTrainer::new(network, samples, &mut rng);
+    trainer.learning_rate = learning_rate;
+    trainer.batch_size = batch_size;
+    println!(
+        "Позиций для обучения: {}, для проверки: {}, скрытый слой: {}, начальное число генератора: {}",
+        trainer.training_len(),
+        trainer.validation_len(),
+        trainer.network().hidden,
+        seed
+    );
+    if trainer.training_len() == 0 {
+        return Err("Нет тихих позиций для обучения".to_string());
+    }
+
+    println!(
+        "Начальное отклонение: {:.6}, на проверке: {:.6}",
+        trainer.training_loss(),
+        trainer.validation_loss()
+    );
+    // Сохраняется сеть с наименьшим отклонением на отложенных позициях
+    let mut best = (trainer.validation_loss(), trainer.network().clone());
+    for epoch in 1..=epochs {
+        let loss = trainer.epoch(&mut rng);
+        let validation_loss = trainer.validation_loss();
+        println!(
+            "Эпоха {}/{}: отклонение {:.6}, на проверке {:.6}",
+            epoch, epochs, loss, validation_loss
+        );
+        if trainer.validation_len() == 0 || validation_loss < best.0 {
+            best = (validation_loss, trainer.network().clone());
+        }
+    }
+    best.1
+        .save(&output, &variant)
+        .map_err(|error| error.to_string())?;
+    println!("Сеть сохранена в {}", output);
+    Ok(())
+}
@@ -0,0 +1,165 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Настройка весов оценки позиции методом Texel по сборникам партий в формате PDN
+//! или наборам позиций, составленным утилитой self-play (файлы .ckds и .csv)
+//!
+//! Использование: tune-weights <правила> <файлы PDN или наборов...> [--skip-plies N]
+//! [--weights файл] [--output файл]
+
+use std::{env, fs, path::Path, process::ExitCode};
+
+use checkers_lib::structs::{evaluators::EvaluationWeights, GameRecord};
+use checkers_tools::{
+    find_variant,
+    structs::{Dataset, TexelTuner, TuningPosition},
+};
+
+/// Сколько первых полуходов каждой партии пропускается по умолчанию: дебют обычно
+/// разыгрывается по жребию или по книге и мало говорит об оценке
+const DEFAULT_SKIP_PLIES: usize = 8;
+
+const USAGE: &str = "Использование: tune-weights <правила> <файлы PDN или наборов...> [--skip-plies N] [--weights файл] [--output файл]";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let Some(name) = args.next() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let variant = match find_variant(&name) {
+        Ok(variant) => variant,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut skip_plies = DEFAULT_SKIP_PLIES;
+    let mut initial = EvaluationWeights::default();
+    let mut output = EvaluationWeights::default_file_name(&variant);
+    let mut files = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--skip-plies" => match args.next().and_then(|plies| plies.parse().ok()) {
+                Some(value) => skip_plies = value,
+                None => {
+                    eprintln!("После --skip-plies должно быть указано число полуходов");
+                    return ExitCode::FAILURE;
+                }
+            },
+            "--weights" => match args.next().map(EvaluationWeights::load) {
+                Some(Ok(weights)) => initial = weights,
+                Some(Err(error)) => {
+                    eprintln!("{}", error);
+                    return ExitCode::FAILURE;
+                }
+                None => {
+                    eprintln!("После --weights должен быть указан файл");
+                    return ExitCode::FAILURE;
+                }
+            },
+            "--output" => match args.next() {
+                Some(value) => output = value,
+                None => {
+                    eprintln!("После --output должен быть указан файл");
+                    return ExitCode::FAILURE;
+                }
+            },
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let mut positions = Vec::new();
+    let (mut games, mut datasets, mut skipped) = (0, 0, 0);
+    for file in &files {
+        let is_dataset = Path::new(file)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| ["ckds", "csv"].contains(&extension));
+        if is_dataset {
+            match Dataset::load(file, variant.clone()) {
+                Ok(dataset) => {
+                    datasets += 1;
+                    positions.extend(
+                        dataset
+                            .entries
+                            .iter()
+                            .filter_map(|entry| TuningPosition::from_entry(entry, &variant)),
+                    )
+                }
+                Err(error) => {
+                    eprintln!("{}: {}", file, error);
+                    return ExitCode::FAILURE;
+                }
+            }
+            continue;
+        }
+        let pdn = match fs::read_to_string(file) {
+            Ok(pdn) => pdn,
+            Err(error) => {
+                eprintln!("Не удалось прочитать {}: {}", file, error);
+                return ExitCode::FAILURE;
+            }
+        };
+        for record in GameRecord::parse_pdn(&variant, &pdn) {
+            match record {
+                Ok(record) if record.initial.variant == variant => {
+                    games += 1;
+                    positions.extend(TuningPosition::from_record(&record, skip_plies));
+                }
+                // Партии других правил и записи с ошибками
+                _ => skipped += 1,
+            }
+        }
+    }
+    let mut tuner = TexelTuner::new(positions);
+    println!(
+        "Партий: {}, пропущено: {}, наборов позиций: {}, позиций для настройки: {}",
+        games,
+        skipped,
+        datasets,
+        tuner.len()
+    );
+    if tuner.is_empty() {
+        eprintln!("Нет позиций с известным результатом партии");
+        return ExitCode::FAILURE;
+    }
+
+    let scale = tuner.fit_scale(&initial);
+    println!(
+        "Коэффициент логистической функции: {:.4}, начальное отклонение: {:.6}",
+        scale,
+        tuner.error(&initial)
+    );
+    let weights = tuner.tune(initial, |error, weights| {
+        println!("Отклонение {:.6}: {:?}", error, weights.to_array());
+    });
+    println!("Настроенные веса: {:?}", weights);
+    match weights.save(&output) {
+        Ok(()) => {
+            println!("Веса сохранены в {}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,64 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{error::Error, fmt, io};
+
+/// Ошибка сохранения или загрузки набора позиций
+#[derive(Debug)]
+pub enum DatasetError {
+    /// Не удалось прочитать или записать файл набора
+    Io(io::Error),
+    /// Файл повреждён или не является набором позиций
+    InvalidFormat(String),
+    /// Набор составлен для другой разновидности игры
+    VariantMismatch { expected: String, found: String },
+    /// Ошибка в строке файла CSV, строки нумеруются с единицы
+    InvalidCsvLine { line: usize, reason: String },
+}
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Ошибка чтения или записи набора позиций: {}", error),
+            Self::InvalidFormat(reason) => {
+                write!(f, "Некорректный файл набора позиций: {}", reason)
+            }
+            Self::VariantMismatch { expected, found } => write!(
+                f,
+                "Набор позиций составлен для \"{}\", а нужен для \"{}\"",
+                found, expected
+            ),
+            Self::InvalidCsvLine { line, reason } => {
+                write!(f, "Строка {} набора позиций: {}", line, reason)
+            }
+        }
+    }
+}
+
+impl Error for DatasetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DatasetError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
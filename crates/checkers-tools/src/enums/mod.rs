@@ -0,0 +1,12 @@
+mod dataset_error;
+mod self_play_error;
+mod self_play_openings;
+mod sprt_status;
+mod tournament_error;
+mod tournament_format;
+
+pub use self::{
+    dataset_error::DatasetError, self_play_error::SelfPlayError,
+    self_play_openings::SelfPlayOpenings, sprt_status::SprtStatus,
+    tournament_error::TournamentError, tournament_format::TournamentFormat,
+};
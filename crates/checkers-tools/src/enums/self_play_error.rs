@@ -0,0 +1,41 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{error::Error, fmt};
+
+/// Ошибка партий движка с самим собой
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelfPlayError {
+    /// Указана нулевая глубина перебора
+    ZeroDepth,
+    /// Все попытки случайного дебюта закончили партию
+    NoOpening { attempts: usize },
+}
+
+impl fmt::Display for SelfPlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroDepth => write!(f, "Глубина перебора должна быть больше нуля"),
+            Self::NoOpening { attempts } => write!(
+                f,
+                "За {} попыток случайные ходы ни разу не оставили партию незаконченной, уменьшите --random-plies",
+                attempts
+            ),
+        }
+    }
+}
+
+impl Error for SelfPlayError {}
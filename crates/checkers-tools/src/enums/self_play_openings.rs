@@ -0,0 +1,35 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+/// Откуда берутся начальные позиции партий с самим собой
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfPlayOpenings {
+    /// Дебюты из колоды по жребию
+    Ballots,
+    /// Несколько случайных ходов из начальной позиции
+    Random,
+}
+
+impl SelfPlayOpenings {
+    /// Разбирает название способа: "ballots" или "random"
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "ballots" => Some(Self::Ballots),
+            "random" => Some(Self::Random),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+
+/// Состояние последовательного теста отношения правдоподобия (SPRT)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtStatus {
+    /// Партий пока недостаточно для решения
+    Continue,
+    /// Принята гипотеза H1: первый участник сильнее на elo1 или больше
+    AcceptH1,
+    /// Принята гипотеза H0: первый участник сильнее не больше, чем на elo0
+    AcceptH0,
+}
+
+impl fmt::Display for SprtStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Continue => write!(f, "решение не принято"),
+            Self::AcceptH1 => write!(f, "H1 принята, изменение усиливает движок"),
+            Self::AcceptH0 => write!(f, "H0 принята, изменение не усиливает движок"),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{fmt, io};
+
+use checkers_lib::enums::{EvaluationWeightsError, NetworkError, OpeningBookError, TablebaseError};
+
+/// Ошибка подготовки турнира движков
+#[derive(Debug)]
+pub enum TournamentError {
+    /// Не удалось прочитать файл с настройками турнира
+    Io(io::Error),
+    /// Файл с настройками не является корректным TOML или содержит неизвестные параметры
+    Parse(toml::de::Error),
+    /// Правила игры не найдены среди встроенных и пользовательских
+    UnknownVariant(String),
+    /// Контроль времени записан не в виде "основное время+добавление" в секундах
+    InvalidTimeControl(String),
+    /// Для турнира нужно хотя бы два участника
+    TooFewEngines,
+    /// В режиме SPRT должно быть ровно два участника
+    SprtNeedsTwoEngines,
+    /// Границы Эло не упорядочены или вероятности ошибок вне интервала (0, 0.5)
+    InvalidSprt,
+    /// Два участника названы одинаково
+    DuplicateEngine(String),
+    /// У участника указана нулевая глубина перебора
+    ZeroDepth(String),
+    /// Не удалось загрузить веса оценки участника
+    EvaluationWeights {
+        engine: String,
+        error: EvaluationWeightsError,
+    },
+    /// Не удалось загрузить нейросеть участника
+    Network { engine: String, error: NetworkError },
+    /// Не удалось загрузить таблицы эндшпиля участника
+    Tablebase {
+        engine: String,
+        error: TablebaseError,
+    },
+    /// Не удалось загрузить дебютную книгу участника
+    OpeningBook {
+        engine: String,
+        error: OpeningBookError,
+    },
+}
+
+impl fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Не удалось прочитать настройки турнира: {}", error),
+            Self::Parse(error) => write!(f, "Некорректные настройки турнира: {}", error),
+            Self::UnknownVariant(error) => write!(f, "{}", error),
+            Self::InvalidTimeControl(time_control) => write!(
+                f,
+                "Некорректный контроль времени \"{}\", ожидается \"основное время+добавление\" в секундах, например \"60+0.5\"",
+                time_control
+            ),
+            Self::TooFewEngines => write!(f, "Для турнира нужно хотя бы два участника"),
+            Self::SprtNeedsTwoEngines => write!(f, "В режиме SPRT должно быть ровно два участника"),
+            Self::InvalidSprt => write!(
+                f,
+                "Некорректные настройки SPRT: нужно elo0 < elo1, alpha и beta от 0 до 0.5"
+            ),
+            Self::DuplicateEngine(name) => write!(f, "Участник \"{}\" указан дважды", name),
+            Self::ZeroDepth(name) => write!(
+                f,
+                "{}: глубина перебора должна быть больше нуля",
+                name
+            ),
+            Self::EvaluationWeights { engine, error } => write!(f, "{}: {}", engine, error),
+            Self::Network { engine, error } => write!(f, "{}: {}", engine, error),
+            Self::Tablebase { engine, error } => write!(f, "{}: {}", engine, error),
+            Self::OpeningBook { engine, error } => write!(f, "{}: {}", engine, error),
+        }
+    }
+}
+
+impl std::error::Error for TournamentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(error),
+            Self::EvaluationWeights { error, .. } => Some(error),
+            Self::Network { error, .. } => Some(error),
+            Self::Tablebase { error, .. } => Some(error),
+            Self::OpeningBook { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TournamentError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for TournamentError {
+    fn from(error: toml::de::Error) -> Self {
+        Self::Parse(error)
+    }
+}
@@ -0,0 +1,40 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use serde::Deserialize;
+
+/// Порядок встреч участников турнира
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TournamentFormat {
+    /// Каждый участник играет с каждым
+    #[default]
+    RoundRobin,
+    /// Первый участник играет со всеми остальными
+    Gauntlet,
+}
+
+impl TournamentFormat {
+    /// Возвращает пары участников, которые играют между собой, по их номерам
+    pub fn pairs(&self, engines: usize) -> Vec<(usize, usize)> {
+        match self {
+            Self::RoundRobin => (0..engines)
+                .flat_map(|first| (first + 1..engines).map(move |second| (first, second)))
+                .collect(),
+            Self::Gauntlet => (1..engines).map(|second| (0, second)).collect(),
+        }
+    }
+}
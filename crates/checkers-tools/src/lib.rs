@@ -0,0 +1,51 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Общие функции и типы консольных утилит: построения таблиц эндшпиля, дебютной книги
+//! и проведения турниров движков
+
+pub mod enums;
+pub mod structs;
+
+use std::io;
+
+use checkers_lib::enums::{CustomVariantError, Variant};
+
+/// Файл с пользовательскими разновидностями игры, ищется в текущей директории
+pub const CUSTOM_VARIANTS_FILE: &str = "variants.toml";
+
+/// Ищет разновидность игры по названию среди встроенных и пользовательских
+pub fn find_variant(name: &str) -> Result<Variant, String> {
+    let mut variants = Variant::ALL.to_vec();
+    match Variant::load_custom(CUSTOM_VARIANTS_FILE) {
+        Ok(custom_variants) => variants.extend(custom_variants),
+        // Файл с пользовательскими правилами необязателен
+        Err(CustomVariantError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(error) => return Err(error.to_string()),
+    }
+    variants
+        .iter()
+        .find(|variant| variant.to_string() == name.trim())
+        .cloned()
+        .ok_or_else(|| {
+            let names: Vec<String> = variants.iter().map(ToString::to_string).collect();
+            format!(
+                "Неизвестные правила \"{}\", доступны: {}",
+                name,
+                names.join(", ")
+            )
+        })
+}
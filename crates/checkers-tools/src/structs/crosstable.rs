@@ -0,0 +1,139 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+
+use crate::structs::{MatchScore, PlayedGame};
+
+/// Турнирная таблица: счёт каждой встречи участников
+#[derive(Debug, Clone)]
+pub struct Crosstable {
+    pub names: Vec<String>,
+    /// Счёт участника из строки против участника из столбца
+    pub scores: Vec<Vec<MatchScore>>,
+}
+
+impl fmt::Display for Crosstable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .names
+            .iter()
+            .enumerate()
+            // В таблице встреч перед именем стоит номер участника
+            .map(|(index, name)| format!("{}. {}", index + 1, name).chars().count())
+            .max()
+            .unwrap_or_default()
+            .max("Участник".chars().count());
+        let mut standings: Vec<usize> = (0..self.names.len()).collect();
+        standings.sort_by(|a, b| self.total(*b).points().total_cmp(&self.total(*a).points()));
+
+        writeln!(
+            f,
+            "{:<5} {:<width$} {:>7} {:>6} {:<14} Эло",
+            "Место", "Участник", "Очки", "Партии", "+/=/-"
+        )?;
+        for (place, &index) in standings.iter().enumerate() {
+            let total = self.total(index);
+            writeln!(
+                f,
+                "{:<5} {:<width$} {:>7} {:>6} {:<14} {}",
+                place + 1,
+                self.names[index],
+                total.points(),
+                total.games(),
+                total.to_string(),
+                Self::elo_str(&total)
+            )?;
+        }
+
+        writeln!(f)?;
+        write!(f, "{:<width$}", "")?;
+        for &index in &standings {
+            write!(f, " {:>7}", index + 1)?;
+        }
+        writeln!(f)?;
+        for &row in &standings {
+            write!(f, "{:<width$}", format!("{}. {}", row + 1, self.names[row]))?;
+            for &column in &standings {
+                let score = self.scores[row][column];
+                let cell = if row == column {
+                    "x".to_string()
+                } else if score.games() == 0 {
+                    "-".to_string()
+                } else {
+                    score.points().to_string()
+                };
+                write!(f, " {:>7}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Встречи:")?;
+        for first in 0..self.names.len() {
+            for second in first + 1..self.names.len() {
+                let score = self.scores[first][second];
+                if score.games() == 0 {
+                    continue;
+                }
+                writeln!(
+                    f,
+                    "{} - {}: {} : {} ({}), Эло: {}",
+                    self.names[first],
+                    self.names[second],
+                    score.points(),
+                    score.reversed().points(),
+                    score,
+                    Self::elo_str(&score)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Crosstable {
+    pub fn new(names: Vec<String>) -> Self {
+        let scores = vec![vec![MatchScore::default(); names.len()]; names.len()];
+        Self { names, scores }
+    }
+
+    /// Учитывает результат сыгранной партии
+    pub fn add(&mut self, played: &PlayedGame) {
+        let result = played.record.result();
+        let (first, second) = (played.game.first, played.game.second);
+        self.scores[first][second].add(result, played.game.first_side);
+        self.scores[second][first].add(result, played.game.first_side.opposite());
+    }
+
+    /// Возвращает суммарный счёт участника против всех соперников
+    pub fn total(&self, index: usize) -> MatchScore {
+        self.scores[index]
+            .iter()
+            .fold(MatchScore::default(), |total, score| MatchScore {
+                wins: total.wins + score.wins,
+                draws: total.draws + score.draws,
+                losses: total.losses + score.losses,
+            })
+    }
+
+    fn elo_str(score: &MatchScore) -> String {
+        score
+            .elo()
+            .map(|elo| elo.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+}
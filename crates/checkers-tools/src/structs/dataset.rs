@@ -0,0 +1,322 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use checkers_lib::{
+    enums::{GameResult, Piece, Side, Variant},
+    structs::GameData,
+};
+
+use crate::enums::DatasetError;
+
+/// Позиция набора: расстановка фигур, оценка перебора и результат партии
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetEntry {
+    /// Содержимое игровых ячеек в порядке их номеров, см. [DatasetEntry::EMPTY] и другие коды
+    pub squares: Vec<u8>,
+    /// Сторона, которая ходит
+    pub side: Side,
+    /// Оценка позиции перебором с точки зрения белых
+    pub score: i32,
+    /// Результат партии, в которой встретилась позиция
+    pub result: GameResult,
+}
+
+impl DatasetEntry {
+    pub const EMPTY: u8 = 0;
+    pub const WHITE_MAN: u8 = 1;
+    pub const WHITE_KING: u8 = 2;
+    pub const BLACK_MAN: u8 = 3;
+    pub const BLACK_KING: u8 = 4;
+
+    /// Создаёт запись по позиции партии
+    pub fn new(game_data: &GameData, score: i32, result: GameResult) -> Self {
+        let mut squares = vec![Self::EMPTY; game_data.playable_positions().count()];
+        for side in [Side::White, Side::Black] {
+            for (position, piece) in game_data.pieces(side) {
+                if let Some(number) = game_data.square_number(*position) {
+                    squares[number - 1] = Self::code(side, *piece);
+                }
+            }
+        }
+        Self {
+            squares,
+            side: game_data.current_move,
+            score,
+            result,
+        }
+    }
+
+    /// Код содержимого ячейки с фигурой
+    pub fn code(side: Side, piece: Piece) -> u8 {
+        match (side, piece) {
+            (Side::White, Piece::Man) => Self::WHITE_MAN,
+            (Side::White, Piece::King) => Self::WHITE_KING,
+            (Side::Black, Piece::Man) => Self::BLACK_MAN,
+            (Side::Black, Piece::King) => Self::BLACK_KING,
+        }
+    }
+
+    /// Записывает позицию в формате FEN
+    pub fn to_fen(&self) -> String {
+        let pieces = |man: u8, king: u8| {
+            let squares: Vec<String> = self
+                .squares
+                .iter()
+                .enumerate()
+                .filter_map(|(index, &code)| match code {
+                    code if code == man => Some((index + 1).to_string()),
+                    code if code == king => Some(format!("K{}", index + 1)),
+                    _ => None,
+                })
+                .collect();
+            squares.join(",")
+        };
+        format!(
+            "{}:W{}:B{}",
+            match self.side {
+                Side::White => "W",
+                Side::Black => "B",
+            },
+            pieces(Self::WHITE_MAN, Self::WHITE_KING),
+            pieces(Self::BLACK_MAN, Self::BLACK_KING)
+        )
+    }
+
+    /// Восстанавливает позицию партии, None - если расстановка невозможна по правилам
+    pub fn game_data(&self, variant: &Variant) -> Option<GameData> {
+        GameData::from_fen(variant.clone(), &self.to_fen()).ok()
+    }
+
+    /// Результат партии для белых: 1 - победа, 0.5 - ничья, 0 - поражение
+    pub fn white_result(&self) -> f64 {
+        match self.result {
+            GameResult::WhiteWins => 1.0,
+            GameResult::BlackWins => 0.0,
+            GameResult::Draw | GameResult::Unfinished => 0.5,
+        }
+    }
+}
+
+/// Набор позиций из партий с оценками и результатами, например для настройки оценки позиции
+///
+/// Сохраняется в двоичном формате или, если у файла расширение ".csv", в текстовом:
+/// строки "FEN,оценка,результат", где оценка и результат (1, 0.5 или 0) - с точки зрения белых
+pub struct Dataset {
+    pub variant: Variant,
+    pub entries: Vec<DatasetEntry>,
+}
+
+impl Dataset {
+    /// Первые байты двоичного файла набора
+    const MAGIC: &'static [u8; 4] = b"CKDS";
+    /// Версия двоичного формата
+    const VERSION: u8 = 1;
+    /// Заголовок файла CSV
+    const CSV_HEADER: &'static str = "fen,score,result";
+
+    pub fn new(variant: Variant) -> Self {
+        Self {
+            variant,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Имя двоичного файла набора для данной разновидности игры
+    pub fn default_file_name(variant: &Variant) -> String {
+        variant.file_name("ckds")
+    }
+
+    /// Загружает набор, составленный для указанной разновидности игры
+    pub fn load(path: impl AsRef<Path>, variant: Variant) -> Result<Self, DatasetError> {
+        let mut reader = BufReader::new(File::open(&path)?);
+        if Self::is_csv(path) {
+            Self::read_csv(&mut reader, variant)
+        } else {
+            Self::read_from(&mut reader, variant)
+        }
+    }
+
+    /// Сохраняет набор в файл
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), DatasetError> {
+        let mut writer = BufWriter::new(File::create(&path)?);
+        if Self::is_csv(path) {
+            self.write_csv(&mut writer)?;
+        } else {
+            self.write_to(&mut writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn is_csv(path: impl AsRef<Path>) -> bool {
+        path.as_ref()
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"))
+    }
+
+    /// Записывает набор в двоичном формате
+    ///
+    /// Заголовок: "CKDS", версия, длина и название правил, количество игровых ячеек
+    /// и количество позиций. Каждая позиция: коды ячеек по два в байте, сторона, которая ходит
+    /// (0 - белые), оценка (i32) и результат (0 - победа чёрных, 1 - ничья, 2 - победа белых)
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let name = self.variant.to_string();
+        let squares = GameData::new(self.variant.clone())
+            .playable_positions()
+            .count();
+        writer.write_all(Self::MAGIC)?;
+        writer.write_all(&[Self::VERSION])?;
+        writer.write_all(&(name.len() as u16).to_le_bytes())?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&(squares as u16).to_le_bytes())?;
+        writer.write_all(&(self.entries.len() as u64).to_le_bytes())?;
+        for entry in &self.entries {
+            let packed: Vec<u8> = entry
+                .squares
+                .chunks(2)
+                .map(|pair| pair[0] | pair.get(1).copied().unwrap_or_default() << 4)
+                .collect();
+            writer.write_all(&packed)?;
+            writer.write_all(&[(entry.side == Side::Black) as u8])?;
+            writer.write_all(&entry.score.to_le_bytes())?;
+            writer.write_all(&[(entry.white_result() * 2.0) as u8])?;
+        }
+        Ok(())
+    }
+
+    /// Читает набор, записанный [Dataset::write_to]
+    pub fn read_from(reader: &mut impl Read, variant: Variant) -> Result<Self, DatasetError> {
+        let invalid = |reason: &str| DatasetError::InvalidFormat(reason.to_string());
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(invalid("неизвестный формат файла"));
+        }
+        let [version] = read_bytes(reader)?;
+        if version != Self::VERSION {
+            return Err(invalid("неподдерживаемая версия формата"));
+        }
+        let name_len = u16::from_le_bytes(read_bytes(reader)?);
+        let mut name = vec![0; name_len as usize];
+        reader.read_exact(&mut name)?;
+        let name = String::from_utf8(name).map_err(|_| invalid("название правил не в UTF-8"))?;
+        if name != variant.to_string() {
+            return Err(DatasetError::VariantMismatch {
+                expected: variant.to_string(),
+                found: name,
+            });
+        }
+
+        let squares = u16::from_le_bytes(read_bytes(reader)?) as usize;
+        if squares != GameData::new(variant.clone()).playable_positions().count() {
+            return Err(invalid("количество игровых ячеек не совпадает с правилами"));
+        }
+        let count = u64::from_le_bytes(read_bytes(reader)?);
+        let mut dataset = Self::new(variant);
+        let mut packed = vec![0; squares.div_ceil(2)];
+        for _ in 0..count {
+            reader.read_exact(&mut packed)?;
+            let codes: Vec<u8> = packed
+                .iter()
+                .flat_map(|byte| [byte & 0x0F, byte >> 4])
+                .take(squares)
+                .collect();
+            if codes.iter().any(|&code| code > DatasetEntry::BLACK_KING) {
+                return Err(invalid("неизвестный код ячейки"));
+            }
+            let [side] = read_bytes(reader)?;
+            let score = i32::from_le_bytes(read_bytes(reader)?);
+            let [result] = read_bytes(reader)?;
+            dataset.entries.push(DatasetEntry {
+                squares: codes,
+                side: Side::from(side != 0),
+                score,
+                result: match result {
+                    0 => GameResult::BlackWins,
+                    1 => GameResult::Draw,
+                    2 => GameResult::WhiteWins,
+                    _ => return Err(invalid("неизвестный результат партии")),
+                },
+            });
+        }
+        Ok(dataset)
+    }
+
+    /// Записывает набор в формате CSV
+    pub fn write_csv(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "{}", Self::CSV_HEADER)?;
+        for entry in &self.entries {
+            writeln!(
+                writer,
+                "{},{},{}",
+                entry.to_fen(),
+                entry.score,
+                entry.white_result()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Читает набор, записанный [Dataset::write_csv]
+    pub fn read_csv(reader: &mut impl BufRead, variant: Variant) -> Result<Self, DatasetError> {
+        let mut dataset = Self::new(variant);
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || (index == 0 && line.trim() == Self::CSV_HEADER) {
+                continue;
+            }
+            let invalid = |reason: String| DatasetError::InvalidCsvLine {
+                line: index + 1,
+                reason,
+            };
+            let mut fields = line.split(',');
+            // В FEN тоже есть запятые, поэтому оценка и результат отделяются с конца
+            let (Some(result), Some(score)) = (fields.next_back(), fields.next_back()) else {
+                return Err(invalid("ожидается \"FEN,оценка,результат\"".to_string()));
+            };
+            let fen = fields.collect::<Vec<_>>().join(",");
+            let game_data = GameData::from_fen(dataset.variant.clone(), &fen)
+                .map_err(|error| invalid(error.to_string()))?;
+            let score = score
+                .trim()
+                .parse()
+                .map_err(|_| invalid(format!("некорректная оценка \"{}\"", score)))?;
+            let result = match result.trim() {
+                "1" => GameResult::WhiteWins,
+                "0.5" => GameResult::Draw,
+                "0" => GameResult::BlackWins,
+                result => return Err(invalid(format!("некорректный результат \"{}\"", result))),
+            };
+            dataset
+                .entries
+                .push(DatasetEntry::new(&game_data, score, result));
+        }
+        Ok(dataset)
+    }
+}
+
+/// Читает из потока массив байтов фиксированной длины
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
@@ -0,0 +1,49 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+
+/// Оценка разницы в рейтинге Эло с погрешностью
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloEstimate {
+    pub elo: f64,
+    /// Половина ширины 95% доверительного интервала
+    pub margin: f64,
+}
+
+impl fmt::Display for EloEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+.0} ± {:.0}", self.elo, self.margin)
+    }
+}
+
+impl EloEstimate {
+    /// Наибольшая разница в рейтинге, которой ограничивается оценка при счёте, близком к 100%
+    const MAX_ELO: f64 = 1000.0;
+
+    /// Переводит ожидаемую долю очков в разницу рейтинга Эло
+    pub fn from_ratio(ratio: f64) -> f64 {
+        if ratio <= 0.0 || ratio >= 1.0 {
+            return Self::MAX_ELO.copysign(ratio - 0.5);
+        }
+        (-400.0 * (1.0 / ratio - 1.0).log10()).clamp(-Self::MAX_ELO, Self::MAX_ELO)
+    }
+
+    /// Переводит разницу рейтинга Эло в ожидаемую долю очков
+    pub fn to_ratio(elo: f64) -> f64 {
+        1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+    }
+}
@@ -0,0 +1,107 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{path::PathBuf, sync::Arc};
+
+use checkers_lib::{
+    enums::Variant,
+    structs::{
+        evaluators::{EvaluationWeights, HandcraftedEvaluator, Network, NetworkEvaluator},
+        Engine, OpeningBook, Tablebase,
+    },
+};
+use serde::Deserialize;
+
+use crate::enums::TournamentError;
+
+/// Настройки движка - участника турнира
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EngineConfig {
+    /// Имя участника в таблице и записи партий
+    pub name: String,
+    /// Глубина перебора. При игре с контролем времени - наибольшая глубина итеративного углубления
+    pub depth: Option<u32>,
+    /// Веса оценки позиции, по умолчанию - встроенные
+    pub weights: Option<EvaluationWeights>,
+    /// Файл весов оценки позиции, например записанный утилитой tune-weights
+    pub weights_file: Option<PathBuf>,
+    /// Файл нейросети, например обученной утилитой train-network. Если указан,
+    /// позиция оценивается сетью, а веса оценки не используются
+    pub network: Option<PathBuf>,
+    /// Файл дебютной книги
+    pub book: Option<PathBuf>,
+    /// Файл таблиц эндшпиля
+    pub tablebase: Option<PathBuf>,
+}
+
+impl EngineConfig {
+    /// Наибольшая глубина перебора при игре с контролем времени, если глубина не указана
+    pub const MAX_TIMED_DEPTH: u32 = 64;
+
+    /// Создаёт движок по настройкам, загружая дебютную книгу и таблицы эндшпиля
+    pub fn build(&self, variant: &Variant, timed: bool) -> Result<Engine, TournamentError> {
+        // С нулевой глубиной движок не выбирает ход, и партия обрывается
+        if self.depth == Some(0) {
+            return Err(TournamentError::ZeroDepth(self.name.clone()));
+        }
+        let depth = self.depth.unwrap_or(if timed {
+            Self::MAX_TIMED_DEPTH
+        } else {
+            Engine::DEFAULT_DEPTH
+        });
+        let mut engine = Engine::new(depth);
+        let weights = match &self.weights_file {
+            Some(path) => Some(EvaluationWeights::load(path).map_err(|error| {
+                TournamentError::EvaluationWeights {
+                    engine: self.name.clone(),
+                    error,
+                }
+            })?),
+            None => self.weights,
+        };
+        if let Some(weights) = weights {
+            engine = engine.with_evaluator(Arc::new(HandcraftedEvaluator::new(weights)));
+        }
+        if let Some(path) = &self.network {
+            let network =
+                Network::load(path, variant.clone()).map_err(|error| TournamentError::Network {
+                    engine: self.name.clone(),
+                    error,
+                })?;
+            engine = engine.with_evaluator(Arc::new(NetworkEvaluator::new(Arc::new(network))));
+        }
+        if let Some(path) = &self.book {
+            let book = OpeningBook::load(path, variant.clone()).map_err(|error| {
+                TournamentError::OpeningBook {
+                    engine: self.name.clone(),
+                    error,
+                }
+            })?;
+            engine = engine.with_book(Arc::new(book));
+        }
+        if let Some(path) = &self.tablebase {
+            let tablebase = Tablebase::load(path, variant.clone()).map_err(|error| {
+                TournamentError::Tablebase {
+                    engine: self.name.clone(),
+                    error,
+                }
+            })?;
+            engine = engine.with_tablebase(Arc::new(tablebase));
+        }
+        Ok(engine)
+    }
+}
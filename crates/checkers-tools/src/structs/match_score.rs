@@ -0,0 +1,102 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+
+use checkers_lib::enums::{GameResult, Side};
+
+use crate::structs::EloEstimate;
+
+/// Счёт матча с точки зрения одного из участников
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MatchScore {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl fmt::Display for MatchScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "+{} ={} -{}", self.wins, self.draws, self.losses)
+    }
+}
+
+impl MatchScore {
+    /// Учитывает результат партии, в которой участник играл данным цветом
+    ///
+    /// Незаконченные партии не учитываются
+    pub fn add(&mut self, result: GameResult, side: Side) {
+        match result {
+            GameResult::Draw => self.draws += 1,
+            GameResult::Unfinished => {}
+            result if result.winner() == Some(side) => self.wins += 1,
+            _ => self.losses += 1,
+        }
+    }
+
+    /// Возвращает счёт с точки зрения соперника
+    pub fn reversed(&self) -> Self {
+        Self {
+            wins: self.losses,
+            draws: self.draws,
+            losses: self.wins,
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Количество очков: победа - одно очко, ничья - половина
+    pub fn points(&self) -> f64 {
+        self.wins as f64 + self.draws as f64 / 2.0
+    }
+
+    /// Доля набранных очков от возможных
+    pub fn ratio(&self) -> Option<f64> {
+        (self.games() > 0).then(|| self.points() / self.games() as f64)
+    }
+
+    /// Оценивает разницу в рейтинге Эло с соперником и её 95% доверительный интервал
+    ///
+    /// Возвращает None, если партий нет или все они закончились одинаково: в этом случае
+    /// разница и её разброс не определены
+    pub fn elo(&self) -> Option<EloEstimate> {
+        let games = self.games() as f64;
+        let ratio = self.ratio()?;
+        let variance = self.variance()?;
+        // Отклонение доли очков для двустороннего 95% интервала
+        let margin = 1.959964 * (variance / games).sqrt();
+        let low = EloEstimate::from_ratio(ratio - margin);
+        let high = EloEstimate::from_ratio(ratio + margin);
+        Some(EloEstimate {
+            elo: EloEstimate::from_ratio(ratio),
+            margin: (high - low) / 2.0,
+        })
+    }
+
+    /// Дисперсия результата одной партии: победа - 1, ничья - 0.5, поражение - 0
+    ///
+    /// Возвращает None, если партий нет или все они закончились одинаково
+    pub fn variance(&self) -> Option<f64> {
+        let ratio = self.ratio()?;
+        let deviation = |result: f64, count: u32| count as f64 * (result - ratio).powi(2);
+        let variance =
+            (deviation(1.0, self.wins) + deviation(0.5, self.draws) + deviation(0.0, self.losses))
+                / self.games() as f64;
+        (variance > 0.0).then_some(variance)
+    }
+}
@@ -0,0 +1,29 @@
+mod crosstable;
+mod dataset;
+mod elo_estimate;
+mod engine_config;
+mod match_score;
+mod network_trainer;
+mod scheduled_game;
+mod self_play;
+mod sprt_config;
+mod texel_tuner;
+mod time_control;
+mod tournament;
+mod tournament_config;
+
+pub use self::{
+    crosstable::Crosstable,
+    dataset::{Dataset, DatasetEntry},
+    elo_estimate::EloEstimate,
+    engine_config::EngineConfig,
+    match_score::MatchScore,
+    network_trainer::{NetworkTrainer, TrainingSample},
+    scheduled_game::{PlayedGame, ScheduledGame},
+    self_play::SelfPlay,
+    sprt_config::SprtConfig,
+    texel_tuner::{TexelTuner, TuningPosition},
+    time_control::TimeControl,
+    tournament::Tournament,
+    tournament_config::TournamentConfig,
+};
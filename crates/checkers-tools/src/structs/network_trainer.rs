@@ -0,0 +1,259 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{enums::Variant, structs::evaluators::Network};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::structs::{DatasetEntry, TuningPosition};
+
+/// Позиция для обучения нейросети: активные входы и ожидаемый результат партии для белых
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingSample {
+    /// Номера активных входов сети, см. [Network::active_inputs]
+    pub inputs: Vec<usize>,
+    /// Ожидаемый результат от 0 до 1
+    pub target: f32,
+}
+
+impl TrainingSample {
+    /// Создаёт позицию для обучения из записи набора позиций, если позиция тихая
+    ///
+    /// Ожидаемый результат смешивает результат партии с весом lambda и оценку перебора,
+    /// переведённую в ожидаемый результат, с весом 1 - lambda
+    pub fn from_entry(
+        entry: &DatasetEntry,
+        variant: &Variant,
+        network: &Network,
+        lambda: f32,
+    ) -> Option<Self> {
+        let game_data = entry.game_data(variant)?;
+        if !TuningPosition::is_quiet(&game_data) {
+            return None;
+        }
+        let score = entry.score as f32 / Network::OUTPUT_SCALE;
+        let target = lambda * entry.white_result() as f32 + (1.0 - lambda) * expected_result(score);
+        Some(Self {
+            inputs: network.active_inputs(&game_data),
+            // В игре "в поддавки" движок инвертирует статическую оценку, поэтому сеть должна
+            // оценивать позицию наоборот
+            target: if game_data.ruleset().is_giveaway() {
+                1.0 - target
+            } else {
+                target
+            },
+        })
+    }
+}
+
+/// Обучение нейросети оценки позиции по набору позиций из партий
+///
+/// Выход сети переводится в ожидаемый результат партии функцией 1 / (1 + 10^(-выход)),
+/// и веса подбираются методом Adam по среднему квадрату отклонения от ожидаемого результата
+/// позиций. Часть позиций откладывается для проверки и в обучении не участвует
+#[derive(Debug, Clone)]
+pub struct NetworkTrainer {
+    network: Network,
+    training: Vec<TrainingSample>,
+    validation: Vec<TrainingSample>,
+    /// Скорость обучения
+    pub learning_rate: f32,
+    /// Количество позиций, по которым вычисляется один шаг обучения
+    pub batch_size: usize,
+    /// Первый и второй моменты градиента всех весов сети в порядке записи в файл: веса входов,
+    /// смещения скрытого слоя, веса и смещение выхода
+    moments: Vec<(f32, f32)>,
+    /// Количество сделанных шагов обучения
+    steps: i32,
+}
+
+impl NetworkTrainer {
+    pub const DEFAULT_HIDDEN: usize = 32;
+    pub const DEFAULT_EPOCHS: usize = 30;
+    pub const DEFAULT_LAMBDA: f32 = 0.5;
+    pub const DEFAULT_LEARNING_RATE: f32 = 0.005;
+    pub const DEFAULT_BATCH_SIZE: usize = 256;
+    /// Доля позиций, откладываемых для проверки
+    pub const VALIDATION_SHARE: f64 = 0.1;
+    /// Коэффициенты затухания моментов метода Adam
+    const BETA1: f32 = 0.9;
+    const BETA2: f32 = 0.999;
+    const EPSILON: f32 = 1e-8;
+
+    /// Создаёт обучение сети, перемешивая позиции и откладывая часть из них для проверки
+    pub fn new(network: Network, mut samples: Vec<TrainingSample>, rng: &mut impl Rng) -> Self {
+        samples.shuffle(rng);
+        let validation_len = (samples.len() as f64 * Self::VALIDATION_SHARE).round() as usize;
+        let training = samples.split_off(validation_len);
+        let parameters = network.input_weights.len()
+            + network.hidden_biases.len()
+            + network.output_weights.len()
+            + 1;
+        Self {
+            network,
+            training,
+            validation: samples,
+            learning_rate: Self::DEFAULT_LEARNING_RATE,
+            batch_size: Self::DEFAULT_BATCH_SIZE,
+            moments: vec![(0.0, 0.0); parameters],
+            steps: 0,
+        }
+    }
+
+    /// Создаёт сеть со случайными начальными весами
+    pub fn random_network(squares: usize, hidden: usize, rng: &mut impl Rng) -> Network {
+        let mut network = Network::new(squares, hidden);
+        // Смещения посередине отрезка активации, чтобы в начале обучения нейроны не были
+        // ни выключены, ни насыщены
+        network.hidden_biases.fill(0.5);
+        let input_range = 1.0 / (squares as f32).sqrt();
+        for weight in &mut network.input_weights {
+            *weight = rng.gen_range(-input_range..input_range);
+        }
+        let output_range = 1.0 / (hidden as f32).sqrt();
+        for weight in &mut network.output_weights {
+            *weight = rng.gen_range(-output_range..output_range);
+        }
+        network
+    }
+
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+
+    pub fn into_network(self) -> Network {
+        self.network
+    }
+
+    /// Количество позиций для обучения
+    pub fn training_len(&self) -> usize {
+        self.training.len()
+    }
+
+    /// Количество отложенных для проверки позиций
+    pub fn validation_len(&self) -> usize {
+        self.validation.len()
+    }
+
+    /// Средний квадрат отклонения на позициях для обучения
+    pub fn training_loss(&self) -> f64 {
+        self.loss(&self.training)
+    }
+
+    /// Средний квадрат отклонения на отложенных позициях
+    pub fn validation_loss(&self) -> f64 {
+        self.loss(&self.validation)
+    }
+
+    /// Одна эпоха обучения: проход по всем позициям для обучения в случайном порядке
+    ///
+    /// Возвращает средний квадрат отклонения на позициях для обучения до изменения весов
+    pub fn epoch(&mut self, rng: &mut impl Rng) -> f64 {
+        let mut samples = std::mem::take(&mut self.training);
+        samples.shuffle(rng);
+        let mut total = 0.0;
+        for batch in samples.chunks(self.batch_size.max(1)) {
+            total += self.step(batch);
+        }
+        let loss = if samples.is_empty() {
+            0.0
+        } else {
+            total / samples.len() as f64
+        };
+        self.training = samples;
+        loss
+    }
+
+    fn loss(&self, samples: &[TrainingSample]) -> f64 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = samples
+            .iter()
+            .map(|sample| {
+                let accumulator = self.network.accumulate(&sample.inputs);
+                let error = expected_result(self.network.output(&accumulator)) - sample.target;
+                (error * error) as f64
+            })
+            .sum();
+        total / samples.len() as f64
+    }
+
+    /// Шаг обучения по группе позиций, возвращает сумму квадратов отклонений до шага
+    fn step(&mut self, batch: &[TrainingSample]) -> f64 {
+        let network = &self.network;
+        let hidden = network.hidden;
+        let mut input_gradient = vec![0.0; network.input_weights.len()];
+        let mut bias_gradient = vec![0.0; hidden];
+        let mut output_gradient = vec![0.0; hidden];
+        let mut output_bias_gradient = 0.0;
+        let mut total = 0.0;
+        for sample in batch {
+            let accumulator = network.accumulate(&sample.inputs);
+            let expected = expected_result(network.output(&accumulator));
+            let error = expected - sample.target;
+            total += (error * error) as f64;
+            // Производная квадрата отклонения по выходу сети
+            let delta = 2.0 * error * expected * (1.0 - expected) * std::f32::consts::LN_10;
+            output_bias_gradient += delta;
+            for neuron in 0..hidden {
+                let value = accumulator.values[neuron];
+                output_gradient[neuron] += delta * Network::activation(value);
+                // Вне отрезка активации производная равна нулю
+                if value <= 0.0 || value >= 1.0 {
+                    continue;
+                }
+                let neuron_delta = delta * network.output_weights[neuron];
+                bias_gradient[neuron] += neuron_delta;
+                for &input in &sample.inputs {
+                    input_gradient[input * hidden + neuron] += neuron_delta;
+                }
+            }
+        }
+
+        let scale = 1.0 / batch.len() as f32;
+        self.steps += 1;
+        let correction1 = 1.0 - Self::BETA1.powi(self.steps);
+        let correction2 = 1.0 - Self::BETA2.powi(self.steps);
+        let learning_rate = self.learning_rate;
+        let parameters = self
+            .network
+            .input_weights
+            .iter_mut()
+            .chain(&mut self.network.hidden_biases)
+            .chain(&mut self.network.output_weights)
+            .chain([&mut self.network.output_bias]);
+        let gradients = input_gradient
+            .into_iter()
+            .chain(bias_gradient)
+            .chain(output_gradient)
+            .chain([output_bias_gradient]);
+        for ((parameter, gradient), (first, second)) in
+            parameters.zip(gradients).zip(&mut self.moments)
+        {
+            let gradient = gradient * scale;
+            *first = Self::BETA1 * *first + (1.0 - Self::BETA1) * gradient;
+            *second = Self::BETA2 * *second + (1.0 - Self::BETA2) * gradient * gradient;
+            *parameter -= learning_rate * (*first / correction1)
+                / ((*second / correction2).sqrt() + Self::EPSILON);
+        }
+        total
+    }
+}
+
+/// Ожидаемый результат партии по выходу сети
+fn expected_result(output: f32) -> f32 {
+    1.0 / (1.0 + 10f32.powf(-output))
+}
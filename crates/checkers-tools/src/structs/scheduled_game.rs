@@ -0,0 +1,53 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::Side,
+    structs::{Ballot, GameRecord},
+};
+
+/// Партия из расписания турнира
+#[derive(Debug, Clone)]
+pub struct ScheduledGame {
+    /// Номер партии в турнире, начиная с единицы
+    pub number: usize,
+    /// Номер первого участника встречи
+    pub first: usize,
+    /// Номер второго участника встречи
+    pub second: usize,
+    /// Дебют, с которого начинается партия
+    pub ballot: Option<Ballot>,
+    /// Цвет, которым играет первый участник
+    pub first_side: Side,
+}
+
+impl ScheduledGame {
+    /// Возвращает номер участника, играющего данным цветом
+    pub fn player(&self, side: Side) -> usize {
+        if side == self.first_side {
+            self.first
+        } else {
+            self.second
+        }
+    }
+}
+
+/// Сыгранная партия турнира
+#[derive(Debug, Clone)]
+pub struct PlayedGame {
+    pub game: ScheduledGame,
+    pub record: GameRecord,
+}
@@ -0,0 +1,253 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use checkers_lib::{
+    enums::{GameResult, Side, Variant},
+    structs::{BallotDeck, Engine, GameData, Move},
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{
+    enums::{SelfPlayError, SelfPlayOpenings},
+    structs::{Dataset, DatasetEntry},
+};
+
+/// Партии движка с самим собой для составления набора позиций
+pub struct SelfPlay {
+    pub variant: Variant,
+    engine: Engine,
+    deck: Option<BallotDeck>,
+    pub openings: SelfPlayOpenings,
+    /// Сколько случайных полуходов делается в начале партии при случайных дебютах
+    pub random_plies: usize,
+    /// Насколько (в сотых долях шашки) ход может быть хуже лучшего, чтобы его можно было
+    /// выбрать случайно. При нуле всегда делается лучший ход
+    pub noise: i32,
+    /// Через сколько полуходов незаконченная партия признаётся ничьей
+    pub max_plies: usize,
+}
+
+impl SelfPlay {
+    pub const DEFAULT_RANDOM_PLIES: usize = 6;
+    pub const DEFAULT_MAX_PLIES: usize = 300;
+    /// Сколько раз позиция должна повториться, чтобы партия была признана ничьей
+    const REPETITIONS_FOR_DRAW: u32 = 3;
+    /// Сколько раз пробуется случайный дебют, прежде чем вернуть ошибку
+    const MAX_OPENING_ATTEMPTS: usize = 100;
+
+    /// Создаёт партии с самим собой для данного движка. Партии начинаются с дебютов колоды,
+    /// если она есть для этих правил, иначе - со случайных ходов
+    pub fn new(variant: Variant, engine: Engine) -> Self {
+        let deck = BallotDeck::for_variant(&variant);
+        let openings = match deck {
+            Some(_) => SelfPlayOpenings::Ballots,
+            None => SelfPlayOpenings::Random,
+        };
+        Self {
+            variant,
+            engine,
+            deck,
+            openings,
+            random_plies: Self::DEFAULT_RANDOM_PLIES,
+            noise: 0,
+            max_plies: Self::DEFAULT_MAX_PLIES,
+        }
+    }
+
+    /// Есть ли для правил колода дебютов
+    pub fn has_ballots(&self) -> bool {
+        self.deck.is_some()
+    }
+
+    /// Играет партии и собирает из них набор позиций без повторов
+    ///
+    /// Партии играются одновременно в threads потоках. Партия с номером n использует
+    /// генератор случайных чисел, инициализированный seed + n, поэтому набор воспроизводим.
+    /// После каждой партии вызывается on_game с количеством сыгранных партий и позиций в наборе.
+    /// При ошибке в одной из партий остальные партии не начинаются
+    pub fn generate(
+        &self,
+        games: usize,
+        threads: usize,
+        seed: u64,
+        mut on_game: impl FnMut(usize, usize),
+    ) -> Result<Dataset, SelfPlayError> {
+        if self.engine.depth() == 0 {
+            return Err(SelfPlayError::ZeroDepth);
+        }
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        let mut results = Vec::new();
+        let mut error = None;
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                let sender = sender.clone();
+                let next = &next;
+                scope.spawn(move || loop {
+                    let number = next.fetch_add(1, Ordering::Relaxed);
+                    if number >= games {
+                        break;
+                    }
+                    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(number as u64));
+                    if sender.send((number, self.play(&mut rng))).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+            let mut unique = HashSet::new();
+            for (number, entries) in receiver {
+                let entries = match entries {
+                    Ok(entries) => entries,
+                    Err(game_error) => {
+                        next.store(games, Ordering::Relaxed);
+                        error = Some(game_error);
+                        break;
+                    }
+                };
+                unique.extend(
+                    entries
+                        .iter()
+                        .map(|entry| (entry.squares.clone(), entry.side)),
+                );
+                results.push((number, entries));
+                on_game(results.len(), unique.len());
+            }
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        // Повторы убираются в порядке номеров партий, чтобы набор не зависел от числа потоков
+        results.sort_by_key(|(number, _)| *number);
+        let mut dataset = Dataset::new(self.variant.clone());
+        let mut unique = HashSet::new();
+        for entry in results.into_iter().flat_map(|(_, entries)| entries) {
+            if unique.insert((entry.squares.clone(), entry.side)) {
+                dataset.entries.push(entry);
+            }
+        }
+        Ok(dataset)
+    }
+
+    /// Играет одну партию и возвращает её позиции с оценками перебора и результатом партии
+    pub fn play(&self, rng: &mut impl Rng) -> Result<Vec<DatasetEntry>, SelfPlayError> {
+        let mut game_data = self.opening(rng)?;
+        let mut positions = Vec::new();
+        let mut repetitions: HashMap<u64, u32> = HashMap::new();
+        let mut plies = 0;
+        let result = loop {
+            if let Some(winner) = game_data.winner() {
+                break GameResult::win(winner);
+            }
+            let repetition = repetitions.entry(game_data.position_hash()).or_default();
+            *repetition += 1;
+            if *repetition >= Self::REPETITIONS_FOR_DRAW || plies >= self.max_plies {
+                break GameResult::Draw;
+            }
+            let Some((piece_move, score)) = self.choose_move(&game_data, rng) else {
+                break GameResult::Draw;
+            };
+            let white_score = match game_data.current_move {
+                Side::White => score,
+                Side::Black => -score,
+            };
+            positions.push((game_data.clone(), white_score));
+            game_data.make_move(game_data.current_move, &piece_move);
+            plies += 1;
+        };
+        Ok(positions
+            .iter()
+            .map(|(game_data, score)| DatasetEntry::new(game_data, *score, result))
+            .collect())
+    }
+
+    /// Возвращает начальную позицию партии: после дебюта из колоды или случайных ходов
+    fn opening(&self, rng: &mut impl Rng) -> Result<GameData, SelfPlayError> {
+        if let (SelfPlayOpenings::Ballots, Some(deck)) = (self.openings, &self.deck) {
+            if let Some(ballot) = deck.ballots.choose(rng) {
+                return Ok(ballot.game_data(self.variant.clone()));
+            }
+        }
+        for _ in 0..Self::MAX_OPENING_ATTEMPTS {
+            let mut game_data = GameData::new(self.variant.clone());
+            for _ in 0..self.random_plies {
+                let moves = game_data.get_all_available_moves();
+                let Some(piece_move) = moves.choose(rng) else {
+                    break;
+                };
+                game_data.make_move(game_data.current_move, piece_move);
+            }
+            // Случайные ходы могут закончить партию, тогда выбираются другие
+            if !game_data.is_game_ended() {
+                return Ok(game_data);
+            }
+        }
+        Err(SelfPlayError::NoOpening {
+            attempts: Self::MAX_OPENING_ATTEMPTS,
+        })
+    }
+
+    /// Выбирает ход и возвращает его вместе с оценкой позиции с точки зрения стороны,
+    /// которая ходит
+    ///
+    /// При ненулевом шуме каждый ход оценивается отдельно, и выбирается случайный из тех,
+    /// что хуже лучшего не больше, чем на величину шума
+    fn choose_move(&self, game_data: &GameData, rng: &mut impl Rng) -> Option<(Move, i32)> {
+        if self.noise <= 0 {
+            let result = self.engine.search(game_data);
+            return result
+                .best_move
+                .map(|piece_move| (piece_move, result.score));
+        }
+        let child_engine = self
+            .engine
+            .with_depth(self.engine.depth().saturating_sub(1));
+        let scored: Vec<(Move, i32)> = game_data
+            .get_all_available_moves()
+            .into_iter()
+            .map(|piece_move| {
+                let mut child = game_data.clone();
+                child.make_move(child.current_move, &piece_move);
+                let score = child_engine.search(&child).score;
+                // При лишних ходах форы после хода снова ходит та же сторона
+                let score = if child.current_move == game_data.current_move {
+                    score
+                } else {
+                    -score
+                };
+                (piece_move, score)
+            })
+            .collect();
+        let best = scored.iter().map(|(_, score)| *score).max()?;
+        let candidates: Vec<&(Move, i32)> = scored
+            .iter()
+            .filter(|(_, score)| *score >= best.saturating_sub(self.noise))
+            .collect();
+        candidates
+            .choose(rng)
+            .map(|(piece_move, _)| (piece_move.clone(), best))
+    }
+}
@@ -0,0 +1,122 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::{
+    enums::SprtStatus,
+    structs::{EloEstimate, MatchScore},
+};
+
+/// Настройки последовательного теста отношения правдоподобия (SPRT)
+///
+/// Тест сравнивает гипотезы H0: первый участник сильнее второго на elo0, и H1: сильнее на elo1,
+/// и останавливает матч, как только отношение правдоподобия (LLR) выходит за одну из границ,
+/// определяемых допустимыми вероятностями ошибок alpha и beta
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SprtConfig {
+    pub elo0: f64,
+    pub elo1: f64,
+    /// Вероятность принять H1, когда верна H0
+    #[serde(default = "SprtConfig::default_error")]
+    pub alpha: f64,
+    /// Вероятность принять H0, когда верна H1
+    #[serde(default = "SprtConfig::default_error")]
+    pub beta: f64,
+}
+
+impl fmt::Display for SprtConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SPRT [{}, {}], alpha {}, beta {}",
+            self.elo0, self.elo1, self.alpha, self.beta
+        )
+    }
+}
+
+impl SprtConfig {
+    /// Наибольшее число партий теста, если оно не указано в настройках турнира
+    pub const DEFAULT_MAX_GAMES: usize = 20000;
+    /// Сколько партий каждого исхода добавляется к счёту при оценке доли очков и дисперсии
+    const PRIOR_GAMES: f64 = 0.5;
+
+    fn default_error() -> f64 {
+        0.05
+    }
+
+    /// Проверяет, что границы Эло и вероятности ошибок допустимы
+    pub fn is_valid(&self) -> bool {
+        let is_probability = |value: f64| value > 0.0 && value < 0.5;
+        self.elo0 < self.elo1 && is_probability(self.alpha) && is_probability(self.beta)
+    }
+
+    /// Нижняя и верхняя границы LLR: при выходе за нижнюю принимается H0, за верхнюю - H1
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    /// Вычисляет логарифм отношения правдоподобия по счёту первого участника
+    ///
+    /// Используется нормальное приближение к трёхисходной модели (победа, ничья, поражение):
+    /// LLR = n (s1 - s0) (2 m - s0 - s1) / (2 σ²), где s0 и s1 - ожидаемые доли очков при elo0
+    /// и elo1, m - набранная доля очков, σ² - дисперсия результата одной партии.
+    ///
+    /// Доля очков и дисперсия оцениваются по счёту, к которому добавлено по
+    /// [SprtConfig::PRIOR_GAMES] партий каждого исхода: иначе, пока все партии закончились
+    /// одинаково (например, одни победы), дисперсия равна нулю и LLR не определён
+    pub fn llr(&self, score: &MatchScore) -> f64 {
+        if score.games() == 0 {
+            return 0.0;
+        }
+        let games = score.games() as f64;
+        let outcomes = [(1.0, score.wins), (0.5, score.draws), (0.0, score.losses)]
+            .map(|(result, count)| (result, count as f64 + Self::PRIOR_GAMES));
+        let total: f64 = outcomes.iter().map(|(_, count)| count).sum();
+        let ratio = outcomes
+            .iter()
+            .map(|(result, count)| result * count)
+            .sum::<f64>()
+            / total;
+        let variance = outcomes
+            .iter()
+            .map(|(result, count)| count * (result - ratio).powi(2))
+            .sum::<f64>()
+            / total;
+        let s0 = EloEstimate::to_ratio(self.elo0);
+        let s1 = EloEstimate::to_ratio(self.elo1);
+        games * (s1 - s0) * (2.0 * ratio - s0 - s1) / (2.0 * variance)
+    }
+
+    /// Возвращает решение теста по счёту первого участника
+    pub fn status(&self, score: &MatchScore) -> SprtStatus {
+        let llr = self.llr(score);
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            SprtStatus::AcceptH1
+        } else if llr <= lower {
+            SprtStatus::AcceptH0
+        } else {
+            SprtStatus::Continue
+        }
+    }
+}
@@ -0,0 +1,212 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::{GameResult, Variant},
+    structs::{
+        evaluators::{EvaluationWeights, HandcraftedEvaluator},
+        GameData, GameRecord,
+    },
+};
+
+use crate::structs::DatasetEntry;
+
+/// Позиция для настройки весов: признаки позиции и результат партии, в которой она встретилась
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TuningPosition {
+    /// Признаки позиции, см. [HandcraftedEvaluator::features]
+    pub features: [i32; EvaluationWeights::LEN],
+    /// Результат партии для белых: 1 - победа, 0.5 - ничья, 0 - поражение.
+    /// В игре "в поддавки" - наоборот, см. [TuningPosition::new]
+    pub result: f64,
+}
+
+impl TuningPosition {
+    /// Создаёт позицию для настройки, если результат партии известен
+    ///
+    /// В игре "в поддавки" движок инвертирует статическую оценку, поэтому веса должны
+    /// предсказывать результат наоборот, и результат партии инвертируется
+    pub fn new(game_data: &GameData, result: GameResult) -> Option<Self> {
+        let result = match result {
+            GameResult::WhiteWins => 1.0,
+            GameResult::Draw => 0.5,
+            GameResult::BlackWins => 0.0,
+            GameResult::Unfinished => return None,
+        };
+        Some(Self {
+            features: HandcraftedEvaluator::features(game_data),
+            result: if game_data.ruleset().is_giveaway() {
+                1.0 - result
+            } else {
+                result
+            },
+        })
+    }
+
+    /// Выбирает позиции партии, пригодные для настройки: после первых skip_plies полуходов
+    /// и только тихие, в которых у стороны, которая ходит, нет взятий
+    ///
+    /// В позициях со взятием статическая оценка не отражает исход размена
+    pub fn from_record(record: &GameRecord, skip_plies: usize) -> Vec<Self> {
+        let result = record.result();
+        record
+            .positions()
+            .iter()
+            .skip(skip_plies)
+            .filter(|game_data| Self::is_quiet(game_data))
+            .filter_map(|game_data| Self::new(game_data, result))
+            .collect()
+    }
+
+    /// Создаёт позицию для настройки из записи набора позиций, если позиция тихая
+    pub fn from_entry(entry: &DatasetEntry, variant: &Variant) -> Option<Self> {
+        let game_data = entry.game_data(variant)?;
+        if !Self::is_quiet(&game_data) {
+            return None;
+        }
+        Self::new(&game_data, entry.result)
+    }
+
+    /// Проверяет, что у стороны, которая ходит, нет взятий
+    pub(crate) fn is_quiet(game_data: &GameData) -> bool {
+        !game_data
+            .get_all_available_moves()
+            .iter()
+            .any(|piece_move| piece_move.is_taking())
+    }
+}
+
+/// Настройка весов оценки позиции методом Texel
+///
+/// Оценка позиции переводится в ожидаемый результат партии логистической функцией
+/// 1 / (1 + 10^(-k * оценка / 400)), и веса подбираются так, чтобы средний квадрат отклонения
+/// ожидаемого результата от действительного был наименьшим
+#[derive(Debug, Clone)]
+pub struct TexelTuner {
+    positions: Vec<TuningPosition>,
+    /// Коэффициент k логистической функции
+    scale: f64,
+}
+
+impl TexelTuner {
+    /// Шаги изменения весов: сначала крупные, затем всё мельче
+    const STEPS: [i32; 5] = [16, 8, 4, 2, 1];
+    /// Границы поиска коэффициента логистической функции
+    const SCALE_RANGE: (f64, f64) = (0.01, 10.0);
+    /// Сколько раз сужается интервал поиска коэффициента
+    const SCALE_ITERATIONS: u32 = 100;
+
+    pub fn new(positions: Vec<TuningPosition>) -> Self {
+        Self {
+            positions,
+            scale: 1.0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Средний квадрат отклонения ожидаемого результата от действительного при данных весах
+    pub fn error(&self, weights: &EvaluationWeights) -> f64 {
+        if self.positions.is_empty() {
+            return 0.0;
+        }
+        let weights = weights.to_array();
+        let total: f64 = self
+            .positions
+            .iter()
+            .map(|position| {
+                let evaluation: i32 = position
+                    .features
+                    .iter()
+                    .zip(weights)
+                    .map(|(feature, weight)| feature * weight)
+                    .sum();
+                let expected = 1.0 / (1.0 + 10f64.powf(-self.scale * evaluation as f64 / 400.0));
+                (position.result - expected).powi(2)
+            })
+            .sum();
+        total / self.positions.len() as f64
+    }
+
+    /// Подбирает коэффициент логистической функции, при котором отклонение для данных весов
+    /// наименьшее, и возвращает его
+    ///
+    /// Коэффициент подбирается один раз до настройки весов: иначе вместо весов менялся бы
+    /// только масштаб оценки
+    pub fn fit_scale(&mut self, weights: &EvaluationWeights) -> f64 {
+        let (mut low, mut high) = Self::SCALE_RANGE;
+        for _ in 0..Self::SCALE_ITERATIONS {
+            let first = low + (high - low) / 3.0;
+            let second = high - (high - low) / 3.0;
+            self.scale = first;
+            let first_error = self.error(weights);
+            self.scale = second;
+            if first_error < self.error(weights) {
+                high = second;
+            } else {
+                low = first;
+            }
+        }
+        self.scale = (low + high) / 2.0;
+        self.scale
+    }
+
+    /// Настраивает веса локальным поиском: каждый вес по очереди увеличивается или уменьшается
+    /// на шаг, пока это уменьшает отклонение, затем шаг уменьшается
+    ///
+    /// После каждого прохода по весам вызывается on_pass с текущим отклонением и весами
+    pub fn tune(
+        &self,
+        initial: EvaluationWeights,
+        mut on_pass: impl FnMut(f64, &EvaluationWeights),
+    ) -> EvaluationWeights {
+        let mut weights = initial.to_array();
+        let mut best_error = self.error(&initial);
+        for step in Self::STEPS {
+            loop {
+                let mut improved = false;
+                for index in 0..EvaluationWeights::LEN {
+                    for delta in [step, -step] {
+                        let mut candidate = weights;
+                        candidate[index] += delta;
+                        let error = self.error(&EvaluationWeights::from_array(candidate));
+                        if error < best_error {
+                            best_error = error;
+                            weights = candidate;
+                            improved = true;
+                            break;
+                        }
+                    }
+                }
+                on_pass(best_error, &EvaluationWeights::from_array(weights));
+                if !improved {
+                    break;
+                }
+            }
+        }
+        EvaluationWeights::from_array(weights)
+    }
+}
@@ -0,0 +1,67 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{fmt, time::Duration};
+
+/// Контроль времени партии: основное время на всю партию и добавление за каждый ход
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    pub base: Duration,
+    pub increment: Duration,
+}
+
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}+{}",
+            self.base.as_secs_f64(),
+            self.increment.as_secs_f64()
+        )
+    }
+}
+
+impl TimeControl {
+    /// На какую долю оставшегося времени рассчитывается один ход
+    const MOVES_TO_GO: u32 = 20;
+
+    /// Разбирает контроль времени вида "60+0.5": основное время и добавление в секундах
+    ///
+    /// Добавление можно не указывать
+    pub fn parse(time_control: &str) -> Option<Self> {
+        let (base, increment) = time_control
+            .trim()
+            .split_once('+')
+            .unwrap_or((time_control.trim(), "0"));
+        let seconds = |value: &str| {
+            value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|value| Duration::try_from_secs_f64(value).ok())
+        };
+        let base = seconds(base)?;
+        (!base.is_zero()).then_some(Self {
+            base,
+            increment: seconds(increment)?,
+        })
+    }
+
+    /// Возвращает время на обдумывание хода при данном остатке времени на часах
+    pub fn move_budget(&self, remaining: Duration) -> Duration {
+        (remaining / Self::MOVES_TO_GO + self.increment).min(remaining / 2)
+    }
+}
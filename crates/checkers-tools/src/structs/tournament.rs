@@ -0,0 +1,279 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Instant,
+};
+
+use checkers_lib::{
+    enums::{GameResult, Side, Variant},
+    structs::{BallotDeck, Engine, GameData, GameRecord},
+};
+
+use crate::{
+    enums::TournamentError,
+    find_variant,
+    structs::{PlayedGame, ScheduledGame, SprtConfig, TimeControl, TournamentConfig},
+};
+
+/// Турнир движков: расписание партий и их проведение
+pub struct Tournament {
+    pub config: TournamentConfig,
+    pub variant: Variant,
+    pub time_control: Option<TimeControl>,
+    engines: Vec<Engine>,
+}
+
+impl Tournament {
+    /// Сколько раз позиция должна повториться, чтобы партия была признана ничьей
+    const REPETITIONS_FOR_DRAW: u32 = 3;
+
+    /// Готовит турнир по настройкам: находит правила и создаёт движки участников
+    pub fn new(config: TournamentConfig) -> Result<Self, TournamentError> {
+        let variant = find_variant(&config.variant).map_err(TournamentError::UnknownVariant)?;
+        let time_control = config
+            .time_control
+            .as_ref()
+            .map(|time_control| {
+                TimeControl::parse(time_control)
+                    .ok_or_else(|| TournamentError::InvalidTimeControl(time_control.clone()))
+            })
+            .transpose()?;
+        if config.engines.len() < 2 {
+            return Err(TournamentError::TooFewEngines);
+        }
+        if let Some(sprt) = &config.sprt {
+            if config.engines.len() != 2 {
+                return Err(TournamentError::SprtNeedsTwoEngines);
+            }
+            if !sprt.is_valid() {
+                return Err(TournamentError::InvalidSprt);
+            }
+        }
+        for (index, engine) in config.engines.iter().enumerate() {
+            if config.engines[..index]
+                .iter()
+                .any(|other| other.name == engine.name)
+            {
+                return Err(TournamentError::DuplicateEngine(engine.name.clone()));
+            }
+        }
+        let engines = config
+            .engines
+            .iter()
+            .map(|engine| engine.build(&variant, time_control.is_some()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            config,
+            variant,
+            time_control,
+            engines,
+        })
+    }
+
+    /// Имена участников в порядке их номеров
+    pub fn names(&self) -> Vec<String> {
+        self.config
+            .engines
+            .iter()
+            .map(|engine| engine.name.clone())
+            .collect()
+    }
+
+    /// Составляет расписание турнира
+    ///
+    /// Если для правил есть колода дебютов, партии начинаются с дебютов колоды, и каждый дебют
+    /// играется дважды со сменой цвета. Иначе все партии начинаются с начальной позиции,
+    /// а цвет меняется через партию. Встречи разных пар чередуются, чтобы промежуточная
+    /// таблица была равномерной
+    pub fn schedule(&self) -> Vec<ScheduledGame> {
+        let pairs = self.config.format.pairs(self.engines.len());
+        let deck = BallotDeck::for_variant(&self.variant);
+        let first_side = self.variant.ruleset().first_move();
+        let default_games = |deck_games: usize| match self.config.sprt {
+            Some(_) => SprtConfig::DEFAULT_MAX_GAMES,
+            None => deck_games,
+        };
+        let openings: Vec<_> = match &deck {
+            Some(deck) => deck
+                .games()
+                .cycle()
+                .take(
+                    self.config
+                        .games
+                        .unwrap_or_else(|| default_games(deck.ballots.len() * 2)),
+                )
+                .map(|(ballot, side)| (Some(ballot.clone()), side))
+                .collect(),
+            None => [first_side, first_side.opposite()]
+                .into_iter()
+                .cycle()
+                .take(self.config.games.unwrap_or_else(|| default_games(2)))
+                .map(|side| (None, side))
+                .collect(),
+        };
+        let mut games = Vec::new();
+        for (ballot, side) in openings {
+            for &(first, second) in &pairs {
+                games.push(ScheduledGame {
+                    number: games.len() + 1,
+                    first,
+                    second,
+                    ballot: ballot.clone(),
+                    first_side: side,
+                });
+            }
+        }
+        games
+    }
+
+    /// Проводит партии расписания, одновременно не больше, чем указано в настройках
+    ///
+    /// После каждой сыгранной партии вызывается on_game; если он возвращает false,
+    /// новые партии не начинаются, а уже начатые доигрываются. Возвращает сыгранные партии
+    /// в порядке их номеров
+    pub fn run(
+        &self,
+        games: &[ScheduledGame],
+        mut on_game: impl FnMut(&PlayedGame) -> bool,
+    ) -> Vec<PlayedGame> {
+        let next = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+        let (sender, receiver) = mpsc::channel();
+        let mut played = Vec::new();
+        thread::scope(|scope| {
+            for _ in 0..self.config.concurrency.max(1) {
+                let sender = sender.clone();
+                let (next, stop) = (&next, &stop);
+                scope.spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        let Some(game) = games.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                            break;
+                        };
+                        let played = PlayedGame {
+                            game: game.clone(),
+                            record: self.play(game),
+                        };
+                        if sender.send(played).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+            for game in receiver {
+                if !on_game(&game) {
+                    stop.store(true, Ordering::Relaxed);
+                }
+                played.push(game);
+            }
+        });
+        played.sort_by_key(|played| played.game.number);
+        played
+    }
+
+    /// Играет партию расписания
+    ///
+    /// Партия, не законченная на доске, завершается ничьей при троекратном повторении
+    /// позиции или по достижении предельного числа полуходов, а при игре с контролем
+    /// времени - поражением стороны, у которой истекло время. Причина записывается
+    /// в тег Termination
+    pub fn play(&self, game: &ScheduledGame) -> GameRecord {
+        let mut game_data = GameData::new(self.variant.clone());
+        let mut record = GameRecord::new(game_data.clone());
+        record.tags = vec![
+            ("Event".to_string(), self.config.event.clone()),
+            ("Round".to_string(), game.number.to_string()),
+            ("White".to_string(), self.name(game.player(Side::White))),
+            ("Black".to_string(), self.name(game.player(Side::Black))),
+        ];
+        if let Some(ballot) = &game.ballot {
+            record
+                .tags
+                .push(("Opening".to_string(), ballot.to_string()));
+            for piece_move in &ballot.moves {
+                game_data.make_move(game_data.current_move, piece_move);
+                record.push(piece_move.clone());
+            }
+        }
+        if let Some(time_control) = self.time_control {
+            record
+                .tags
+                .push(("TimeControl".to_string(), time_control.to_string()));
+        }
+
+        let mut clocks = HashMap::from([
+            (Side::White, self.time_control.map(|tc| tc.base)),
+            (Side::Black, self.time_control.map(|tc| tc.base)),
+        ]);
+        let mut repetitions: HashMap<u64, u32> = HashMap::new();
+        let termination = loop {
+            if game_data.is_game_ended() {
+                break None;
+            }
+            let repetition = repetitions.entry(game_data.position_hash()).or_default();
+            *repetition += 1;
+            if *repetition >= Self::REPETITIONS_FOR_DRAW {
+                record.declared_result = Some(GameResult::Draw);
+                break Some("Троекратное повторение позиции");
+            }
+            if record.moves.len() >= self.config.max_plies {
+                record.declared_result = Some(GameResult::Draw);
+                break Some("Предельное число полуходов");
+            }
+
+            let side = game_data.current_move;
+            let engine = &self.engines[game.player(side)];
+            let piece_move = match (self.time_control, clocks[&side]) {
+                (Some(time_control), Some(remaining)) => {
+                    let start = Instant::now();
+                    let piece_move =
+                        engine.best_move_within(&game_data, time_control.move_budget(remaining));
+                    let elapsed = start.elapsed();
+                    if elapsed > remaining {
+                        record.declared_result = Some(GameResult::win(side.opposite()));
+                        break Some("Истекло время");
+                    }
+                    clocks.insert(side, Some(remaining - elapsed + time_control.increment));
+                    piece_move
+                }
+                _ => engine.best_move(&game_data),
+            };
+            // Ходы есть, раз партия не закончена, и движок всегда выбирает один из них
+            let Some(piece_move) = piece_move else {
+                break None;
+            };
+            game_data.make_move(side, &piece_move);
+            record.push(piece_move);
+        };
+        if let Some(termination) = termination {
+            record
+                .tags
+                .push(("Termination".to_string(), termination.to_string()));
+        }
+        record
+    }
+
+    fn name(&self, index: usize) -> String {
+        self.config.engines[index].name.clone()
+    }
+}
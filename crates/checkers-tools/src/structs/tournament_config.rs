@@ -0,0 +1,86 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    enums::{TournamentError, TournamentFormat},
+    structs::{EngineConfig, SprtConfig},
+};
+
+/// Настройки турнира движков, читаются из файла TOML
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TournamentConfig {
+    /// Название турнира, записывается в тег Event партий
+    #[serde(default = "TournamentConfig::default_event")]
+    pub event: String,
+    /// Название правил игры
+    pub variant: String,
+    #[serde(default)]
+    pub format: TournamentFormat,
+    /// Сколько партий играет каждая пара участников. По умолчанию - каждый дебют колоды
+    /// дважды, со сменой цвета, а в режиме SPRT - [SprtConfig::DEFAULT_MAX_GAMES]
+    pub games: Option<usize>,
+    /// Последовательный тест: матч двух участников останавливается, как только тест
+    /// принимает одну из гипотез
+    pub sprt: Option<SprtConfig>,
+    /// Контроль времени вида "60+0.5" в секундах. Без него движки играют на заданную глубину
+    pub time_control: Option<String>,
+    /// Через сколько полуходов незаконченная партия признаётся ничьей
+    #[serde(default = "TournamentConfig::default_max_plies")]
+    pub max_plies: usize,
+    /// Сколько партий играется одновременно
+    #[serde(default = "TournamentConfig::default_concurrency")]
+    pub concurrency: usize,
+    /// Файл, в который записываются все партии
+    #[serde(default = "TournamentConfig::default_pdn")]
+    pub pdn: String,
+    /// Файл, в который записывается таблица турнира
+    #[serde(default = "TournamentConfig::default_report")]
+    pub report: String,
+    #[serde(rename = "engine")]
+    pub engines: Vec<EngineConfig>,
+}
+
+impl TournamentConfig {
+    /// Загружает настройки турнира из файла
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TournamentError> {
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn default_event() -> String {
+        "Турнир движков".to_string()
+    }
+
+    fn default_max_plies() -> usize {
+        300
+    }
+
+    fn default_concurrency() -> usize {
+        1
+    }
+
+    fn default_pdn() -> String {
+        "tournament.pdn".to_string()
+    }
+
+    fn default_report() -> String {
+        "tournament.txt".to_string()
+    }
+}
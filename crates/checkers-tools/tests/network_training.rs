@@ -0,0 +1,85 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::{GameResult, Piece, Side, Variant},
+    structs::{evaluators::Network, GameData},
+};
+use checkers_tools::structs::{DatasetEntry, NetworkTrainer, TrainingSample};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn training_reduces_loss() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let network = NetworkTrainer::random_network(32, 8, &mut rng);
+    // Белая шашка на 18-м поле выигрывает, чёрная на 15-м проигрывает, иначе - ничья
+    let white = Network::piece_input(18, Side::White, Piece::Man);
+    let black = Network::piece_input(15, Side::Black, Piece::Man);
+    let mut samples = Vec::new();
+    for _ in 0..50 {
+        samples.extend([
+            TrainingSample {
+                inputs: vec![white],
+                target: 1.0,
+            },
+            TrainingSample {
+                inputs: vec![black],
+                target: 0.0,
+            },
+            TrainingSample {
+                inputs: vec![white, black],
+                target: 0.5,
+            },
+        ]);
+    }
+    let mut trainer = NetworkTrainer::new(network, samples, &mut rng);
+    assert_eq!(trainer.validation_len(), 15);
+    assert_eq!(trainer.training_len(), 135);
+    trainer.batch_size = 16;
+    trainer.learning_rate = 0.02;
+    let initial = trainer.validation_loss();
+    for _ in 0..50 {
+        trainer.epoch(&mut rng);
+    }
+    assert!(trainer.validation_loss() < initial / 10.0);
+    assert!(trainer.training_loss() < 0.01);
+}
+
+#[test]
+fn sample_target_mixes_score_and_result() {
+    let game_data = GameData::from_fen(Variant::English, "W:W22,27:B14").unwrap();
+    let network = Network::new(32, 4);
+    let entry = DatasetEntry::new(&game_data, 0, GameResult::WhiteWins);
+    let sample = |lambda| {
+        TrainingSample::from_entry(&entry, &Variant::English, &network, lambda)
+            .unwrap()
+            .target
+    };
+    assert_eq!(sample(1.0), 1.0);
+    assert_eq!(sample(0.0), 0.5);
+    assert_eq!(sample(0.5), 0.75);
+    assert_eq!(
+        TrainingSample::from_entry(&entry, &Variant::English, &network, 0.5)
+            .unwrap()
+            .inputs,
+        network.active_inputs(&game_data)
+    );
+
+    // У белых есть взятие, такая позиция для обучения не подходит
+    let taking = GameData::from_fen(Variant::English, "W:W18:B14").unwrap();
+    let entry = DatasetEntry::new(&taking, 0, GameResult::WhiteWins);
+    assert!(TrainingSample::from_entry(&entry, &Variant::English, &network, 0.5).is_none());
+}
@@ -0,0 +1,125 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashSet;
+
+use checkers_lib::{
+    enums::{GameResult, Variant},
+    structs::{Engine, GameData},
+};
+use checkers_tools::{
+    enums::{DatasetError, SelfPlayError, SelfPlayOpenings},
+    structs::{Dataset, DatasetEntry, SelfPlay},
+};
+
+#[test]
+fn dataset_is_saved_in_both_formats() {
+    let variant = Variant::English;
+    let game_data = GameData::from_fen(variant.clone(), "B:W18,K27,31:B5,K12").unwrap();
+    let entry = DatasetEntry::new(&game_data, -150, GameResult::WhiteWins);
+    assert_eq!(entry.to_fen(), "B:W18,K27,31:B5,K12");
+    let mut dataset = Dataset::new(variant.clone());
+    dataset.entries = vec![
+        entry,
+        DatasetEntry::new(&GameData::new(variant.clone()), 0, GameResult::Draw),
+    ];
+
+    for file_name in ["checkers-dataset.ckds", "checkers-dataset.csv"] {
+        let path = std::env::temp_dir().join(file_name);
+        dataset.save(&path).unwrap();
+        let loaded = Dataset::load(&path, variant.clone()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.entries, dataset.entries, "{}", file_name);
+    }
+}
+
+#[test]
+fn turkish_dataset_uses_all_cells() {
+    // В турецких шашках игра идёт на всех 64 ячейках доски
+    let variant = Variant::Turkish;
+    let mut self_play = SelfPlay::new(variant.clone(), Engine::new(1));
+    assert_eq!(self_play.openings, SelfPlayOpenings::Random);
+    self_play.max_plies = 40;
+    let dataset = self_play.generate(2, 1, 3, |_, _| {}).unwrap();
+    assert!(!dataset.entries.is_empty());
+    assert!(dataset
+        .entries
+        .iter()
+        .all(|entry| entry.squares.len() == 64));
+    let initial = DatasetEntry::new(&GameData::new(variant.clone()), 0, GameResult::Draw);
+    assert_eq!(
+        initial.game_data(&variant).unwrap().position_hash(),
+        GameData::new(variant.clone()).position_hash()
+    );
+
+    for file_name in [
+        "checkers-dataset-turkish.ckds",
+        "checkers-dataset-turkish.csv",
+    ] {
+        let path = std::env::temp_dir().join(file_name);
+        dataset.save(&path).unwrap();
+        let loaded = Dataset::load(&path, variant.clone()).unwrap();
+        assert!(matches!(
+            Dataset::load(&path, Variant::English),
+            Err(DatasetError::VariantMismatch { .. } | DatasetError::InvalidCsvLine { .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.entries, dataset.entries, "{}", file_name);
+    }
+}
+
+#[test]
+fn self_play_is_reproducible_and_unique() {
+    let mut self_play = SelfPlay::new(Variant::English, Engine::new(2));
+    assert_eq!(self_play.openings, SelfPlayOpenings::Ballots);
+    self_play.noise = 30;
+    self_play.max_plies = 60;
+
+    let dataset = self_play.generate(4, 2, 7, |_, _| {}).unwrap();
+    assert!(!dataset.entries.is_empty());
+    let positions: HashSet<_> = dataset
+        .entries
+        .iter()
+        .map(|entry| (entry.squares.clone(), entry.side))
+        .collect();
+    assert_eq!(positions.len(), dataset.entries.len());
+    assert!(dataset
+        .entries
+        .iter()
+        .all(|entry| entry.result != GameResult::Unfinished));
+
+    let again = self_play.generate(4, 1, 7, |_, _| {}).unwrap();
+    assert_eq!(again.entries, dataset.entries);
+}
+
+#[test]
+fn invalid_settings_are_rejected() {
+    let self_play = SelfPlay::new(Variant::English, Engine::new(0));
+    assert_eq!(
+        self_play.generate(1, 1, 0, |_, _| {}).err(),
+        Some(SelfPlayError::ZeroDepth)
+    );
+
+    // Со столькими случайными ходами партия успевает закончиться: при обязательном взятии
+    // одна из сторон рано или поздно лишается всех фигур
+    let mut self_play = SelfPlay::new(Variant::Russian, Engine::new(1));
+    self_play.openings = SelfPlayOpenings::Random;
+    self_play.random_plies = 10_000;
+    assert!(matches!(
+        self_play.generate(2, 2, 0, |_, _| {}),
+        Err(SelfPlayError::NoOpening { .. })
+    ));
+}
@@ -0,0 +1,247 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::{GameResult, Side},
+    structs::GameRecord,
+};
+use checkers_tools::{
+    enums::{SprtStatus, TournamentError, TournamentFormat},
+    structs::{EloEstimate, MatchScore, SprtConfig, TimeControl, Tournament, TournamentConfig},
+};
+
+#[test]
+fn elo_is_estimated_from_score() {
+    let mut score = MatchScore::default();
+    for result in [
+        GameResult::WhiteWins,
+        GameResult::WhiteWins,
+        GameResult::Draw,
+    ] {
+        score.add(result, Side::White);
+    }
+    score.add(GameResult::WhiteWins, Side::Black);
+    assert_eq!(score.points(), 2.5);
+
+    let elo = score.elo().unwrap();
+    assert!((EloEstimate::to_ratio(elo.elo) - 0.625).abs() < 1e-9);
+    let reversed = score.reversed().elo().unwrap();
+    assert!((elo.elo + reversed.elo).abs() < 1e-9);
+    assert!((elo.margin - reversed.margin).abs() < 1e-9);
+
+    // Разброс не определён, если все партии закончились одинаково
+    assert_eq!(MatchScore::default().elo(), None);
+    let draws = MatchScore {
+        draws: 10,
+        ..Default::default()
+    };
+    assert_eq!(draws.elo(), None);
+}
+
+#[test]
+fn time_control_is_parsed() {
+    let time_control = TimeControl::parse("60+0.5").unwrap();
+    assert_eq!(time_control.base.as_millis(), 60_000);
+    assert_eq!(time_control.increment.as_millis(), 500);
+    assert_eq!(TimeControl::parse("10").unwrap().increment.as_millis(), 0);
+    assert_eq!(TimeControl::parse("0+1"), None);
+    assert_eq!(TimeControl::parse("минута"), None);
+}
+
+#[test]
+fn schedule_swaps_colours_on_each_ballot() {
+    let config: TournamentConfig = toml::from_str(
+        r#"
+        variant = "Английские шашки"
+        format = "gauntlet"
+        games = 4
+
+        [[engine]]
+        name = "A"
+        [[engine]]
+        name = "B"
+        [[engine]]
+        name = "C"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.format, TournamentFormat::Gauntlet);
+    let tournament = Tournament::new(config).unwrap();
+    let games = tournament.schedule();
+    assert_eq!(games.len(), 8);
+    assert!(games.iter().all(|game| game.first == 0));
+    let first_pair: Vec<_> = games.iter().filter(|game| game.second == 1).collect();
+    assert_eq!(first_pair[0].ballot, first_pair[1].ballot);
+    assert_ne!(first_pair[0].first_side, first_pair[1].first_side);
+    assert_ne!(first_pair[1].ballot, first_pair[2].ballot);
+}
+
+#[test]
+fn sprt_accepts_hypothesis_by_score() {
+    let sprt = SprtConfig {
+        elo0: 0.0,
+        elo1: 10.0,
+        alpha: 0.05,
+        beta: 0.05,
+    };
+    let (lower, upper) = sprt.bounds();
+    assert!((lower + upper).abs() < 1e-9);
+
+    let even = MatchScore {
+        wins: 10,
+        draws: 20,
+        losses: 10,
+    };
+    assert_eq!(sprt.status(&even), SprtStatus::Continue);
+    let stronger = MatchScore {
+        wins: 300,
+        draws: 400,
+        losses: 200,
+    };
+    assert_eq!(sprt.status(&stronger), SprtStatus::AcceptH1);
+    assert_eq!(sprt.status(&stronger.reversed()), SprtStatus::AcceptH0);
+    assert!(sprt.llr(&stronger) > sprt.llr(&even));
+}
+
+#[test]
+fn sprt_stops_when_all_games_end_alike() {
+    let sprt = SprtConfig {
+        elo0: 0.0,
+        elo1: 5.0,
+        alpha: 0.05,
+        beta: 0.05,
+    };
+    let score = |wins, draws| MatchScore {
+        wins,
+        draws,
+        losses: 0,
+    };
+    assert_eq!(sprt.status(&score(1, 0)), SprtStatus::Continue);
+    // Одни победы: H1 принимается задолго до наибольшего числа партий
+    let games = (1..=100)
+        .find(|&wins| sprt.status(&score(wins, 0)) == SprtStatus::AcceptH1)
+        .unwrap();
+    assert!(games < 50, "{}", games);
+    assert_eq!(
+        sprt.status(&score(games, 0).reversed()),
+        SprtStatus::AcceptH0
+    );
+    // Одни ничьи: изменение не усиливает движок
+    assert!((1..=1000).any(|draws| sprt.status(&score(0, draws)) == SprtStatus::AcceptH0));
+}
+
+/// Турнир двух участников, играющих на глубину 1, по английским правилам
+fn two_engine_tournament(settings: &str) -> Result<Tournament, TournamentError> {
+    let config: TournamentConfig = toml::from_str(&format!(
+        r#"
+        event = "Тест"
+        variant = "Английские шашки"
+        {}
+
+        [[engine]]
+        name = "A"
+        depth = 1
+        [[engine]]
+        name = "B"
+        depth = 1
+        "#,
+        settings
+    ))
+    .unwrap();
+    Tournament::new(config)
+}
+
+/// Значение тега партии
+fn tag<'a>(record: &'a GameRecord, name: &str) -> Option<&'a str> {
+    record
+        .tags
+        .iter()
+        .find(|(tag, _)| tag == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn games_are_played_and_adjudicated() {
+    let tournament = two_engine_tournament("").unwrap();
+    let games = tournament.schedule();
+    let record = tournament.play(&games[0]);
+    let names = tournament.names();
+    for side in [Side::White, Side::Black] {
+        let tag_name = if side == Side::White {
+            "White"
+        } else {
+            "Black"
+        };
+        assert_eq!(
+            tag(&record, tag_name),
+            Some(names[games[0].player(side)].as_str())
+        );
+    }
+    assert_eq!(tag(&record, "Round"), Some("1"));
+    assert!(tag(&record, "Opening").is_some());
+    // Движки без случайности быстро повторяют позицию
+    assert_eq!(record.result(), GameResult::Draw);
+    assert_eq!(
+        tag(&record, "Termination"),
+        Some("Троекратное повторение позиции")
+    );
+    assert!(record
+        .to_pdn(&[])
+        .contains("[Termination \"Троекратное повторение позиции\"]"));
+
+    let tournament = two_engine_tournament("max_plies = 10").unwrap();
+    let record = tournament.play(&tournament.schedule()[0]);
+    assert_eq!(record.moves.len(), 10);
+    assert_eq!(record.result(), GameResult::Draw);
+    assert_eq!(
+        tag(&record, "Termination"),
+        Some("Предельное число полуходов")
+    );
+
+    // За микросекунду не успеть сделать ни одного хода
+    let tournament = two_engine_tournament(r#"time_control = "0.000001+0""#).unwrap();
+    let game = &tournament.schedule()[0];
+    let record = tournament.play(game);
+    let ballot_plies = game.ballot.as_ref().unwrap().moves.len();
+    assert_eq!(record.moves.len(), ballot_plies);
+    let side = record.positions().last().unwrap().current_move;
+    assert_eq!(record.result(), GameResult::win(side.opposite()));
+    assert_eq!(tag(&record, "Termination"), Some("Истекло время"));
+    assert_eq!(tag(&record, "TimeControl"), Some("0.000001+0"));
+
+    let played = tournament.run(&tournament.schedule()[..2], |_| true);
+    assert_eq!(played.len(), 2);
+}
+
+#[test]
+fn zero_depth_is_rejected() {
+    let config: TournamentConfig = toml::from_str(
+        r#"
+        variant = "Английские шашки"
+
+        [[engine]]
+        name = "A"
+        depth = 0
+        [[engine]]
+        name = "B"
+        "#,
+    )
+    .unwrap();
+    assert!(matches!(
+        Tournament::new(config),
+        Err(TournamentError::ZeroDepth(name)) if name == "A"
+    ));
+}
@@ -0,0 +1,93 @@
+/*
+Copyright 2023 Сырцев Вадим Игоревич
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use checkers_lib::{
+    enums::{GameResult, Side, Variant},
+    structs::{evaluators::EvaluationWeights, Engine, GameData, GameRecord},
+};
+use checkers_tools::structs::{TexelTuner, TuningPosition};
+
+#[test]
+fn tuning_reduces_prediction_error() {
+    // Лишняя шашка выигрывает, а равный материал - ничья
+    let position = |man: i32, result: f64| TuningPosition {
+        features: [man, 0, 0, 0, 0],
+        result,
+    };
+    let mut positions = Vec::new();
+    for _ in 0..10 {
+        positions.extend([position(1, 1.0), position(-1, 0.0), position(0, 0.5)]);
+    }
+    positions.extend([position(1, 0.5), position(-1, 0.5)]);
+    let mut tuner = TexelTuner::new(positions);
+    let initial = EvaluationWeights {
+        man: 10,
+        ..Default::default()
+    };
+    tuner.fit_scale(&EvaluationWeights::default());
+    let tuned = tuner.tune(initial, |_, _| {});
+    assert!(tuner.error(&tuned) < tuner.error(&initial));
+    assert!(tuned.man > initial.man);
+    // Остальные признаки во всех позициях нулевые и не влияют на отклонение
+    assert_eq!(tuned.king, initial.king);
+
+    let path = std::env::temp_dir().join("checkers-tuned-weights.toml");
+    tuned.save(&path).unwrap();
+    assert_eq!(EvaluationWeights::load(&path).unwrap(), tuned);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn giveaway_result_is_inverted() {
+    // В поддавках лишние шашки мешают: партию выигрывают чёрные
+    let fen = "W:W21,22,23:B1";
+    let russian = GameData::from_fen(Variant::Russian, fen).unwrap();
+    let giveaway = GameData::from_fen(Variant::Giveaway, fen).unwrap();
+    assert_eq!(
+        TuningPosition::new(&russian, GameResult::BlackWins)
+            .unwrap()
+            .result,
+        0.0
+    );
+    assert_eq!(
+        TuningPosition::new(&giveaway, GameResult::BlackWins)
+            .unwrap()
+            .result,
+        1.0
+    );
+
+    // Партия в поддавки до конца: все позиции получают инвертированный результат
+    let mut game_data = GameData::from_fen(Variant::Giveaway, "W:W21,22,23,25:B1,2,6").unwrap();
+    let mut record = GameRecord::new(game_data.clone());
+    let engine = Engine::new(4);
+    while let Some(piece_move) = engine.best_move(&game_data) {
+        game_data.make_move(game_data.current_move, &piece_move);
+        record.push(piece_move);
+    }
+    let winner = game_data.winner().unwrap();
+    let expected = if winner == Side::White { 0.0 } else { 1.0 };
+    let positions = TuningPosition::from_record(&record, 0);
+    assert!(!positions.is_empty());
+    assert!(positions.iter().all(|position| position.result == expected));
+
+    // Со встроенными весами, которые движок инвертирует в поддавках, отклонение меньше,
+    // чем с весами противоположного знака
+    let mut tuner = TexelTuner::new(positions);
+    let weights = EvaluationWeights::default();
+    let negated = EvaluationWeights::from_array(weights.to_array().map(|weight| -weight));
+    tuner.fit_scale(&weights);
+    assert!(tuner.error(&weights) < tuner.error(&negated));
+}